@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use kzg_poly_commit_exploration::{
+    domain::Domain,
+    polynomial::Polynomial,
+    scalar::Scalar,
+    trusted_setup::{ParallelSetupArtifactsGenerator, SetupArtifact},
+};
+
+fn generate_polynomial(degree: u32) -> Polynomial {
+    let coefficients: Vec<Scalar> = (0..(degree + 1))
+        .map(|i| Scalar::from(5).pow(i as usize).add(&Scalar::from(10)))
+        .collect();
+    Polynomial::try_from(coefficients).unwrap()
+}
+
+fn generate_setup_artifacts(degree: u32) -> Vec<SetupArtifact> {
+    let mut s_bytes = [0; 32]; // Secret is a 256-bit scalar
+    s_bytes.copy_from_slice(&(0..32).collect::<Vec<u8>>());
+    ParallelSetupArtifactsGenerator::new(s_bytes).generate((degree + 1) as usize)
+}
+
+fn bench_all_proofs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("all_proofs");
+    group
+        .measurement_time(Duration::from_secs_f32(10.0))
+        .sample_size(10);
+
+    // Test with different domain sizes, the polynomial having as many coefficients as the domain has points
+    let sizes = [16, 64, 256];
+
+    for size in sizes.iter() {
+        let degree = (*size - 1) as u32;
+        let polynomial = generate_polynomial(degree);
+        let setup_artifacts = generate_setup_artifacts(degree);
+        let domain = Domain::new(*size).unwrap();
+
+        group.bench_with_input(
+            BenchmarkId::new("fk20", size),
+            &(&polynomial, &domain, &setup_artifacts),
+            |b, (p, domain, artifacts)| {
+                b.iter(|| p.prove_all_points(domain, artifacts).unwrap());
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("separate_proofs", size),
+            &(&polynomial, &domain, &setup_artifacts),
+            |b, (p, domain, artifacts)| {
+                b.iter(|| {
+                    for point in domain.elements() {
+                        let evaluation = p.evaluate(point).unwrap();
+                        let _proof = evaluation.generate_proof(p, artifacts).unwrap();
+                    }
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("fk20_cosets_of_4", size),
+            &(&polynomial, &domain, &setup_artifacts),
+            |b, (p, domain, artifacts)| {
+                b.iter(|| p.prove_all_cosets(domain, 4, artifacts).unwrap());
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_all_proofs);
+criterion_main!(benches);
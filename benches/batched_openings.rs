@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use kzg_poly_commit_exploration::{
+    curves::G1Point,
+    polynomial::{MultiPointEvaluation, Polynomial, gwc::GwcProof, shplonk::ShplonkProof},
+    scalar::Scalar,
+    trusted_setup::{ParallelSetupArtifactsGenerator, SetupArtifact},
+};
+
+const DEGREE: u32 = 50;
+
+fn generate_polynomial(index: u32) -> Polynomial {
+    let coefficients: Vec<Scalar> = (0..(DEGREE + 1))
+        .map(|i| {
+            Scalar::from(5)
+                .pow(i as usize)
+                .add(&Scalar::from(10 + index as i128))
+        })
+        .collect();
+    Polynomial::try_from(coefficients).unwrap()
+}
+
+fn generate_setup_artifacts() -> Vec<SetupArtifact> {
+    let mut s_bytes = [0; 32]; // Secret is a 256-bit scalar
+    s_bytes.copy_from_slice(&(0..32).collect::<Vec<u8>>());
+    ParallelSetupArtifactsGenerator::new(s_bytes).generate(DEGREE as usize + 1)
+}
+
+/// Opens the `i`-th polynomial at 1, 2 or 3 points among 3 shared points, as PLONK-style provers do
+fn generate_openings(
+    count: u32,
+    artifacts: &[SetupArtifact],
+) -> (Vec<Polynomial>, Vec<G1Point>, Vec<MultiPointEvaluation>) {
+    let polynomials: Vec<Polynomial> = (0..count).map(generate_polynomial).collect();
+    let commitments = polynomials
+        .iter()
+        .map(|p| p.commit(artifacts).unwrap())
+        .collect();
+    let evaluations = polynomials
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let points: Vec<Scalar> = (0..(i % 3 + 1))
+                .map(|j| Scalar::from(7).pow(j + 1))
+                .collect();
+            MultiPointEvaluation::new(p, &points).unwrap()
+        })
+        .collect();
+    (polynomials, commitments, evaluations)
+}
+
+fn bench_batched_openings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batched_openings");
+    group
+        .measurement_time(Duration::from_secs_f32(10.0))
+        .sample_size(20);
+
+    let artifacts = generate_setup_artifacts();
+
+    // Test with different numbers of polynomials of degree 50
+    let counts = [1, 4, 16];
+
+    for count in counts.iter() {
+        let (polynomials, commitments, evaluations) = generate_openings(*count, &artifacts);
+
+        group.bench_with_input(
+            BenchmarkId::new("gwc_proof", count),
+            &(&polynomials, &evaluations),
+            |b, (polynomials, evaluations)| {
+                b.iter(|| GwcProof::generate(polynomials, evaluations, &artifacts).unwrap());
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("shplonk_proof", count),
+            &(&polynomials, &evaluations),
+            |b, (polynomials, evaluations)| {
+                b.iter(|| ShplonkProof::generate(polynomials, evaluations, &artifacts).unwrap());
+            },
+        );
+
+        let gwc = GwcProof::generate(&polynomials, &evaluations, &artifacts).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("gwc_verification", count),
+            &(&commitments, &evaluations),
+            |b, (commitments, evaluations)| {
+                b.iter(|| assert!(gwc.verify(commitments, evaluations, &artifacts).unwrap()));
+            },
+        );
+        let shplonk = ShplonkProof::generate(&polynomials, &evaluations, &artifacts).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("shplonk_verification", count),
+            &(&commitments, &evaluations),
+            |b, (commitments, evaluations)| {
+                b.iter(|| {
+                    assert!(
+                        shplonk
+                            .verify(commitments, evaluations, &artifacts)
+                            .unwrap()
+                    )
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_batched_openings);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use kzg_poly_commit_exploration::{
+    curves::{G1Point, G2Point, bilinear_map},
+    polynomial::Polynomial,
+    scalar::Scalar,
+    trusted_setup::ParallelSetupArtifactsGenerator,
+};
+
+fn generate_scalar() -> Scalar {
+    let mut s_bytes = [0; 32];
+    s_bytes.copy_from_slice(&(0..32).collect::<Vec<u8>>());
+    Scalar::from_be_bytes(s_bytes)
+}
+
+fn bench_generator_multiplication(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generator_multiplication");
+    group
+        .measurement_time(Duration::from_secs_f32(10.0))
+        .sample_size(100);
+
+    let scalar = generate_scalar();
+    // Warm up the lazily initialized tables so that their construction is not measured
+    let _ = G1Point::from_scalar(scalar.clone());
+    let _ = G2Point::from_scalar(scalar.clone());
+
+    group.bench_function("g1_fixed_base_table", |b| {
+        b.iter(|| G1Point::from_scalar(scalar.clone()))
+    });
+    group.bench_function("g1_double_and_add", |b| {
+        b.iter(|| G1Point::generator().mult(&scalar))
+    });
+    group.bench_function("g2_fixed_base_table", |b| {
+        b.iter(|| G2Point::from_scalar(scalar.clone()))
+    });
+    group.bench_function("g2_double_and_add", |b| {
+        b.iter(|| G2Point::generator().mult(&scalar))
+    });
+
+    group.finish();
+}
+
+/// Verification of a single evaluation, whose two generator multiplications use the tables or double-and-add
+fn bench_verification_generator_multiplication(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verification_generator_multiplication");
+    group
+        .measurement_time(Duration::from_secs_f32(10.0))
+        .sample_size(100);
+
+    let setup_artifacts = ParallelSetupArtifactsGenerator::new([7; 32]).generate(16);
+    let polynomial = Polynomial::try_from((1..=16).collect::<Vec<i128>>()).unwrap();
+    let commitment = polynomial.commit(&setup_artifacts).unwrap();
+    let evaluation = polynomial.evaluate(generate_scalar()).unwrap();
+    let proof = evaluation
+        .generate_proof(&polynomial, &setup_artifacts)
+        .unwrap();
+    assert!(
+        evaluation
+            .verify_proof(&proof, &commitment, &setup_artifacts)
+            .unwrap()
+    );
+
+    group.bench_function("verify_proof_fixed_base_table", |b| {
+        b.iter(|| {
+            evaluation
+                .verify_proof(&proof, &commitment, &setup_artifacts)
+                .unwrap()
+        })
+    });
+    // Same pairing check as `Evaluation::verify_proof`, the generators being multiplied without their tables
+    group.bench_function("verify_proof_double_and_add", |b| {
+        b.iter(|| {
+            let lhs = bilinear_map(
+                &proof,
+                &setup_artifacts[1]
+                    .g2
+                    .sub(&G2Point::generator().mult(&evaluation.point)),
+            );
+            let rhs = bilinear_map(
+                &commitment.sub(&G1Point::generator().mult(&evaluation.result)),
+                &G2Point::generator(),
+            );
+            lhs == rhs
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_generator_multiplication,
+    bench_verification_generator_multiplication
+);
+criterion_main!(benches);
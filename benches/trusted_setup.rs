@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use kzg_poly_commit_exploration::trusted_setup::SetupArtifactsGenerator;
+use kzg_poly_commit_exploration::trusted_setup::{
+    ParallelSetupArtifactsGenerator, SetupArtifactsGenerator,
+};
 
 fn bench_trusted_setup_generation(c: &mut Criterion) {
     let mut group = c.benchmark_group("trusted_setup_generation");
@@ -28,6 +30,17 @@ fn bench_trusted_setup_generation(c: &mut Criterion) {
                 });
             },
         );
+        group.bench_with_input(
+            BenchmarkId::new("parallel_setup_generation", degree),
+            degree,
+            |b, &degree| {
+                b.iter(|| {
+                    // Benchmark: Generate setup artifacts over multiple threads
+                    let _setup_artifacts = ParallelSetupArtifactsGenerator::new(s_bytes)
+                        .generate((degree + 1) as usize);
+                });
+            },
+        );
     }
 
     group.finish();
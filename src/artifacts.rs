@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    fs,
+    io::{BufReader, Write},
+    path::Path,
+};
+
+use super::{
+    curves::{G1Point, G2Point},
+    polynomial::{Evaluation, MultiPointEvaluation, Polynomial, degree_bound::DegreeBoundProof},
+    trusted_setup::Setup,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommitmentArtifact {
+    /// Hash of the setup used for the commitment
+    pub setup_hash: String,
+    pub polynomial: Polynomial,
+    pub commitment: G1Point,
+    /// Proof that the degree of the polynomial is lower or equal to a bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degree_bound: Option<DegreeBoundProof>,
+}
+
+/// Openings of a committed polynomial, accumulated over the evaluations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvaluationArtifact {
+    /// Hash of the setup used for the proofs
+    pub setup_hash: String,
+    /// Commitment of the evaluated polynomial
+    pub commitment: G1Point,
+    /// Openings at a single point, at most one per point
+    pub openings: Vec<Opening>,
+    /// Openings at several points proven by a single proof, at most one per set of points
+    pub aggregated_openings: Vec<AggregatedOpening>,
+}
+
+/// Points of a setup needed to verify the proofs of single evaluations, without the points needed to prove them
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifierKeyArtifact {
+    /// Hash of the setup the points are taken from
+    pub setup_hash: String,
+    /// Generator of G1
+    pub g1: G1Point,
+    /// Generator of G2
+    pub g2: G2Point,
+    /// Secret times the generator of G2
+    pub s_g2: G2Point,
+}
+
+impl VerifierKeyArtifact {
+    /// Extracts the verifier key of a setup
+    ///
+    /// * `setup` - Setup with at least 2 artifacts
+    pub fn from_setup(setup: &Setup) -> Result<Self, anyhow::Error> {
+        if setup.artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "The verifier key requires at least 2 setup artifacts"
+            ));
+        }
+        Ok(Self {
+            setup_hash: setup.hash().to_owned(),
+            g1: setup.artifacts[0].g1.clone(),
+            g2: setup.artifacts[0].g2.clone(),
+            s_g2: setup.artifacts[1].g2.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Opening {
+    pub evaluation: Evaluation,
+    pub proof: G1Point,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregatedOpening {
+    pub evaluation: MultiPointEvaluation,
+    pub proof: G1Point,
+}
+
+impl EvaluationArtifact {
+    /// Creates an artifact without any opening
+    ///
+    /// * `setup_hash` - Hash of the setup used for the proofs
+    /// * `commitment` - Commitment of the evaluated polynomial
+    pub fn new(setup_hash: String, commitment: G1Point) -> Self {
+        Self {
+            setup_hash,
+            commitment,
+            openings: vec![],
+            aggregated_openings: vec![],
+        }
+    }
+
+    /// Adds an opening, replacing any previous opening at the same point
+    ///
+    /// * `opening` - Opening to add
+    pub fn add_opening(&mut self, opening: Opening) {
+        self.openings
+            .retain(|o| o.evaluation.point != opening.evaluation.point);
+        self.openings.push(opening);
+    }
+
+    /// Adds an aggregated opening, replacing any previous aggregated opening at the same points
+    ///
+    /// * `opening` - Aggregated opening to add
+    pub fn add_aggregated_opening(&mut self, opening: AggregatedOpening) {
+        self.aggregated_openings
+            .retain(|o| o.evaluation.points() != opening.evaluation.points());
+        self.aggregated_openings.push(opening);
+    }
+}
+
+/// Checks that an artifact has been generated with a given setup
+///
+/// * `setup` - Expected setup
+/// * `artifact_name` - Name of the artifact, used in the error message
+/// * `setup_hash` - Hash of the setup recorded in the artifact
+pub fn check_setup_hash(
+    setup: &Setup,
+    artifact_name: &str,
+    setup_hash: &str,
+) -> Result<(), anyhow::Error> {
+    if setup.hash() != setup_hash {
+        return Err(anyhow::anyhow!(
+            "The {artifact_name} artifact has been generated with the setup {setup_hash} while the current setup is {}, generate it again.",
+            setup.hash()
+        ));
+    }
+    Ok(())
+}
+
+/// Reads a JSON artifact
+///
+/// * `path` - Path of the artifact
+/// * `missing_message` - Error message if the artifact does not exist
+pub fn read_artifact<T: DeserializeOwned>(
+    path: &Path,
+    missing_message: &str,
+) -> Result<T, anyhow::Error> {
+    if !fs::exists(path)? {
+        return Err(anyhow::anyhow!(
+            "{missing_message} Expected at \"{}\".",
+            path.display()
+        ));
+    }
+    let file = fs::File::open(path)?;
+    let reader = BufReader::new(file);
+    serde_json::from_reader(reader).map_err(|e| {
+        anyhow::anyhow!(
+            "Unable to read the artifact \"{}\", it may have been generated by a previous version: {e}",
+            path.display()
+        )
+    })
+}
+
+/// Writes an artifact in JSON, the parent folder is created if needed and any previous artifact is replaced
+///
+/// * `path` - Path of the artifact
+/// * `artifact` - Artifact to write
+pub fn write_artifact<T: Serialize>(path: &Path, artifact: &T) -> Result<(), anyhow::Error> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::exists(path)? {
+        fs::remove_file(path)?;
+    }
+    let stringified_artifact = serde_json::to_string(artifact)?;
+    let mut file = fs::File::create(path)?;
+    file.write_all(stringified_artifact.as_bytes())?;
+    Ok(())
+}
@@ -1,13 +1,37 @@
-use std::ops::Deref;
+use std::{ops::Deref, sync::LazyLock};
 
 use serde::{
     Deserialize, Serialize,
     de::{self, Visitor},
 };
 
+use thiserror::Error;
+
 use crate::scalar::Scalar;
 
-#[derive(Debug)]
+/// Error of the decoding of a compressed point
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PointDecodingError {
+    #[error("Invalid length, expected {expected}, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    /// The flags are inconsistent or the coordinate is not a field element
+    #[error("Invalid encoding of the compressed point")]
+    InvalidEncoding,
+    /// The coordinate is a field element but no point of the curve has it
+    #[error("The point is not on the curve")]
+    NotOnCurve,
+}
+
+impl From<blst::BLST_ERROR> for PointDecodingError {
+    fn from(value: blst::BLST_ERROR) -> Self {
+        match value {
+            blst::BLST_ERROR::BLST_POINT_NOT_ON_CURVE => PointDecodingError::NotOnCurve,
+            _ => PointDecodingError::InvalidEncoding,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct G1Point(blst::blst_p1);
 
 impl From<blst::blst_p1> for G1Point {
@@ -16,6 +40,33 @@ impl From<blst::blst_p1> for G1Point {
     }
 }
 
+impl TryFrom<blst::blst_p1_affine> for G1Point {
+    type Error = anyhow::Error;
+
+    /// Try to create a point from affine coordinates, the point must be on the curve and in the G1 subgroup
+    ///
+    /// * `value` - Point in affine coordinates
+    fn try_from(value: blst::blst_p1_affine) -> Result<Self, Self::Error> {
+        if !unsafe { blst::blst_p1_affine_on_curve(&value) } {
+            return Err(anyhow::anyhow!("Point is not on the G1 curve"));
+        }
+        if !unsafe { blst::blst_p1_affine_in_g1(&value) } {
+            return Err(anyhow::anyhow!("Point is not in the G1 subgroup"));
+        }
+        let mut out = blst::blst_p1::default();
+        unsafe {
+            blst::blst_p1_from_affine(&mut out, &value);
+        };
+        Ok(out.into())
+    }
+}
+
+impl PartialEq for G1Point {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { blst::blst_p1_is_equal(self.as_raw_ptr(), other.as_raw_ptr()) }
+    }
+}
+
 impl G1Point {
     /// Returns the wrapped point as raw pointer
     fn as_raw_ptr(&self) -> *const blst::blst_p1 {
@@ -26,38 +77,81 @@ impl G1Point {
     ///
     /// * `a` - Scalar to project
     pub fn from_i128(a: i128) -> Self {
-        let scalar = blst_scalar_from_i128_as_abs(a);
-        let mut out = blst::blst_p1::default();
+        Self::from_scalar(Scalar::from_i128(a))
+    }
+
+    /// Project a scalar to the G1 curve using the generator.
+    ///
+    /// The multiplication uses the precomputed table of the generator, the table is built on first use.
+    ///
+    /// * `a` - Scalar to project
+    pub fn from_scalar(a: Scalar) -> Self {
+        G1_GENERATOR_TABLE.mult(&a)
+    }
+
+    /// Returns the compressed representation of the point
+    pub fn to_compressed_bytes(&self) -> [u8; 48] {
+        let mut compressed_p1 = [0; 48];
         unsafe {
-            blst::blst_p1_mult(
-                &mut out,
-                blst::blst_p1_generator(),
-                scalar.b.as_ptr(),
-                scalar.b.len() * 8,
-            );
+            blst::blst_p1_compress(compressed_p1.as_mut_ptr(), self.as_raw_ptr());
         };
-        if a < 0 {
-            unsafe {
-                blst::blst_p1_cneg(&mut out, true);
-            }
+        compressed_p1
+    }
+
+    /// Creates a point from its compressed representation, the point must be on the curve
+    ///
+    /// * `v` - Compressed point of length 48
+    pub fn from_compressed_bytes(v: &[u8]) -> Result<Self, anyhow::Error> {
+        Ok(Self::decode_compressed_bytes(v)?)
+    }
+
+    /// Creates a point from its compressed representation and checks that it is in the G1 subgroup
+    ///
+    /// * `v` - Compressed point of length 48
+    pub fn from_compressed_bytes_in_group(v: &[u8]) -> Result<Self, anyhow::Error> {
+        let point = Self::from_compressed_bytes(v)?;
+        if !point.is_in_group() {
+            return Err(anyhow::anyhow!("Point is not in the G1 subgroup"));
         }
-        out.into()
+        Ok(point)
     }
 
-    /// Project a scalar to the G1 curve using the generator
+    /// Creates a point from its compressed representation, telling malformed encodings from points off the curve
     ///
-    /// * `a` - Scalar to project
-    pub fn from_scalar(a: Scalar) -> Self {
-        let mut out = blst::blst_p1::default();
+    /// * `v` - Compressed point of length 48
+    pub fn decode_compressed_bytes(v: &[u8]) -> Result<Self, PointDecodingError> {
+        if v.len() != 48 {
+            return Err(PointDecodingError::InvalidLength {
+                expected: 48,
+                actual: v.len(),
+            });
+        }
+
+        let mut compressed_p1 = [0u8; 48];
+        compressed_p1.copy_from_slice(v);
+        let mut uncompressed_p1_affine = blst::blst_p1_affine::default();
         unsafe {
-            blst::blst_p1_mult(
-                &mut out,
-                blst::blst_p1_generator(),
-                a.to_le_bytes().as_ptr(),
-                256,
-            );
+            match blst::blst_p1_uncompress(&mut uncompressed_p1_affine, compressed_p1.as_ptr()) {
+                blst::BLST_ERROR::BLST_SUCCESS => Ok(()),
+                other => Err(PointDecodingError::from(other)),
+            }
+        }?;
+
+        let mut uncompressed_p1 = blst::blst_p1::default();
+        unsafe {
+            blst::blst_p1_from_affine(&mut uncompressed_p1, &uncompressed_p1_affine);
         };
-        out.into()
+        Ok(uncompressed_p1.into())
+    }
+
+    /// Returns true if the point belongs to the G1 subgroup
+    pub fn is_in_group(&self) -> bool {
+        unsafe { blst::blst_p1_in_g1(self.as_raw_ptr()) }
+    }
+
+    /// Returns the generator of the G1 group
+    pub fn generator() -> Self {
+        unsafe { *blst::blst_p1_generator() }.into()
     }
 
     /// Subtract two points and give the result as a new point
@@ -96,16 +190,19 @@ impl G1Point {
     }
 }
 
+impl Deref for G1Point {
+    type Target = blst::blst_p1;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 impl Serialize for G1Point {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut compressed_p1 = [0; 48];
-        unsafe {
-            blst::blst_p1_compress(compressed_p1.as_mut_ptr(), self.as_raw_ptr());
-        };
-        serializer.serialize_bytes(&compressed_p1)
+        serializer.serialize_bytes(&self.to_compressed_bytes())
     }
 }
 
@@ -116,33 +213,6 @@ impl<'de> Deserialize<'de> for G1Point {
     {
         struct G1PointVisitor;
 
-        fn bytes_to_blst_p1(v: &[u8]) -> Result<G1Point, anyhow::Error> {
-            if v.len() != 48 {
-                return Err(anyhow::anyhow!(
-                    "Invalid length, expected 48, got {}",
-                    v.len()
-                ));
-            }
-
-            let mut compressed_p1 = [0u8; 48];
-            compressed_p1.copy_from_slice(v);
-            let mut uncompressed_p1_affine = blst::blst_p1_affine::default();
-            unsafe {
-                match blst::blst_p1_uncompress(&mut uncompressed_p1_affine, compressed_p1.as_ptr())
-                {
-                    blst::BLST_ERROR::BLST_SUCCESS => Ok(()),
-                    other => Err(other),
-                }
-            }
-            .map_err(|err| anyhow::anyhow!("Got error while uncompressing: {err:?}"))?;
-
-            let mut uncompressed_p1 = blst::blst_p1::default();
-            unsafe {
-                blst::blst_p1_from_affine(&mut uncompressed_p1, &uncompressed_p1_affine);
-            };
-            Ok(uncompressed_p1.into())
-        }
-
         impl<'de> Visitor<'de> for G1PointVisitor {
             type Value = G1Point;
 
@@ -160,21 +230,21 @@ impl<'de> Deserialize<'de> for G1Point {
                     elements.push(a)
                 }
 
-                bytes_to_blst_p1(&elements).map_err(de::Error::custom)
+                G1Point::from_compressed_bytes(&elements).map_err(de::Error::custom)
             }
 
             fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                bytes_to_blst_p1(v).map_err(de::Error::custom)
+                G1Point::from_compressed_bytes(v).map_err(de::Error::custom)
             }
 
             fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                bytes_to_blst_p1(v).map_err(de::Error::custom)
+                G1Point::from_compressed_bytes(v).map_err(de::Error::custom)
             }
         }
 
@@ -182,7 +252,7 @@ impl<'de> Deserialize<'de> for G1Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct G2Point(blst::blst_p2);
 
 impl From<blst::blst_p2> for G2Point {
@@ -191,6 +261,33 @@ impl From<blst::blst_p2> for G2Point {
     }
 }
 
+impl TryFrom<blst::blst_p2_affine> for G2Point {
+    type Error = anyhow::Error;
+
+    /// Try to create a point from affine coordinates, the point must be on the curve and in the G2 subgroup
+    ///
+    /// * `value` - Point in affine coordinates
+    fn try_from(value: blst::blst_p2_affine) -> Result<Self, Self::Error> {
+        if !unsafe { blst::blst_p2_affine_on_curve(&value) } {
+            return Err(anyhow::anyhow!("Point is not on the G2 curve"));
+        }
+        if !unsafe { blst::blst_p2_affine_in_g2(&value) } {
+            return Err(anyhow::anyhow!("Point is not in the G2 subgroup"));
+        }
+        let mut out = blst::blst_p2::default();
+        unsafe {
+            blst::blst_p2_from_affine(&mut out, &value);
+        };
+        Ok(out.into())
+    }
+}
+
+impl PartialEq for G2Point {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { blst::blst_p2_is_equal(self.as_raw_ptr(), other.as_raw_ptr()) }
+    }
+}
+
 impl G2Point {
     /// Returns the wrapped point as raw pointer
     fn as_raw_ptr(&self) -> *const blst::blst_p2 {
@@ -201,38 +298,81 @@ impl G2Point {
     ///
     /// * `a` - Scalar to project
     pub fn from_i128(a: i128) -> Self {
-        let scalar = blst_scalar_from_i128_as_abs(a);
-        let mut out = blst::blst_p2::default();
+        Self::from_scalar(Scalar::from_i128(a))
+    }
+
+    /// Project a scalar to the G2 curve using the generator.
+    ///
+    /// The multiplication uses the precomputed table of the generator, the table is built on first use.
+    ///
+    /// * `a` - Scalar to project
+    pub fn from_scalar(a: Scalar) -> Self {
+        G2_GENERATOR_TABLE.mult(&a)
+    }
+
+    /// Returns the compressed representation of the point
+    pub fn to_compressed_bytes(&self) -> [u8; 96] {
+        let mut compressed_p2 = [0; 96];
         unsafe {
-            blst::blst_p2_mult(
-                &mut out,
-                blst::blst_p2_generator(),
-                scalar.b.as_ptr(),
-                scalar.b.len() * 8,
-            );
+            blst::blst_p2_compress(compressed_p2.as_mut_ptr(), self.as_raw_ptr());
         };
-        if a < 0 {
-            unsafe {
-                blst::blst_p2_cneg(&mut out, true);
-            }
+        compressed_p2
+    }
+
+    /// Creates a point from its compressed representation, the point must be on the curve
+    ///
+    /// * `v` - Compressed point of length 96
+    pub fn from_compressed_bytes(v: &[u8]) -> Result<Self, anyhow::Error> {
+        Ok(Self::decode_compressed_bytes(v)?)
+    }
+
+    /// Creates a point from its compressed representation and checks that it is in the G2 subgroup
+    ///
+    /// * `v` - Compressed point of length 96
+    pub fn from_compressed_bytes_in_group(v: &[u8]) -> Result<Self, anyhow::Error> {
+        let point = Self::from_compressed_bytes(v)?;
+        if !point.is_in_group() {
+            return Err(anyhow::anyhow!("Point is not in the G2 subgroup"));
         }
-        out.into()
+        Ok(point)
     }
 
-    /// Project a scalar to the G2 curve using the generator
+    /// Creates a point from its compressed representation, telling malformed encodings from points off the curve
     ///
-    /// * `a` - Scalar to project
-    pub fn from_scalar(a: Scalar) -> Self {
-        let mut out = blst::blst_p2::default();
+    /// * `v` - Compressed point of length 96
+    pub fn decode_compressed_bytes(v: &[u8]) -> Result<Self, PointDecodingError> {
+        if v.len() != 96 {
+            return Err(PointDecodingError::InvalidLength {
+                expected: 96,
+                actual: v.len(),
+            });
+        }
+
+        let mut compressed_p2 = [0u8; 96];
+        compressed_p2.copy_from_slice(v);
+        let mut uncompressed_p2_affine = blst::blst_p2_affine::default();
         unsafe {
-            blst::blst_p2_mult(
-                &mut out,
-                blst::blst_p2_generator(),
-                a.to_le_bytes().as_ptr(),
-                256,
-            );
+            match blst::blst_p2_uncompress(&mut uncompressed_p2_affine, compressed_p2.as_ptr()) {
+                blst::BLST_ERROR::BLST_SUCCESS => Ok(()),
+                other => Err(PointDecodingError::from(other)),
+            }
+        }?;
+
+        let mut uncompressed_p2 = blst::blst_p2::default();
+        unsafe {
+            blst::blst_p2_from_affine(&mut uncompressed_p2, &uncompressed_p2_affine);
         };
-        out.into()
+        Ok(uncompressed_p2.into())
+    }
+
+    /// Returns true if the point belongs to the G2 subgroup
+    pub fn is_in_group(&self) -> bool {
+        unsafe { blst::blst_p2_in_g2(self.as_raw_ptr()) }
+    }
+
+    /// Returns the generator of the G2 group
+    pub fn generator() -> Self {
+        unsafe { *blst::blst_p2_generator() }.into()
     }
 
     /// Subtract two points and give the result as a new point
@@ -247,6 +387,28 @@ impl G2Point {
         };
         out.into()
     }
+
+    /// Add two points and give the result as a new point
+    ///
+    /// * `b` - G2 point to add to self
+    pub fn add(&self, b: &Self) -> Self {
+        let mut out = blst::blst_p2::default();
+        unsafe {
+            blst::blst_p2_add_or_double(&mut out, self.as_raw_ptr(), b.as_raw_ptr());
+        };
+        out.into()
+    }
+
+    /// Multiply a point by a scalar and give the result as a new point
+    ///
+    /// * `a` - Scalar that will multiply self
+    pub fn mult(&self, a: &Scalar) -> Self {
+        let mut out = blst::blst_p2::default();
+        unsafe {
+            blst::blst_p2_mult(&mut out, self.as_raw_ptr(), a.to_le_bytes().as_ptr(), 256);
+        };
+        out.into()
+    }
 }
 
 impl Deref for G2Point {
@@ -261,11 +423,7 @@ impl Serialize for G2Point {
     where
         S: serde::Serializer,
     {
-        let mut compressed_p2 = [0; 96];
-        unsafe {
-            blst::blst_p2_compress(compressed_p2.as_mut_ptr(), self.as_raw_ptr());
-        };
-        serializer.serialize_bytes(&compressed_p2)
+        serializer.serialize_bytes(&self.to_compressed_bytes())
     }
 }
 
@@ -276,33 +434,6 @@ impl<'de> Deserialize<'de> for G2Point {
     {
         struct G2PointVisitor;
 
-        fn bytes_to_blst_p2(v: &[u8]) -> Result<G2Point, anyhow::Error> {
-            if v.len() != 96 {
-                return Err(anyhow::anyhow!(
-                    "Invalid length, expected 96, got {}",
-                    v.len()
-                ));
-            }
-
-            let mut compressed_p2 = [0u8; 96];
-            compressed_p2.copy_from_slice(v);
-            let mut uncompressed_p2_affine = blst::blst_p2_affine::default();
-            unsafe {
-                match blst::blst_p2_uncompress(&mut uncompressed_p2_affine, compressed_p2.as_ptr())
-                {
-                    blst::BLST_ERROR::BLST_SUCCESS => Ok(()),
-                    other => Err(other),
-                }
-            }
-            .map_err(|err| anyhow::anyhow!("Got error while uncompressing: {err:?}"))?;
-
-            let mut uncompressed_p2 = blst::blst_p2::default();
-            unsafe {
-                blst::blst_p2_from_affine(&mut uncompressed_p2, &uncompressed_p2_affine);
-            };
-            Ok(uncompressed_p2.into())
-        }
-
         impl<'de> Visitor<'de> for G2PointVisitor {
             type Value = G2Point;
 
@@ -320,21 +451,21 @@ impl<'de> Deserialize<'de> for G2Point {
                     elements.push(a)
                 }
 
-                bytes_to_blst_p2(&elements).map_err(de::Error::custom)
+                G2Point::from_compressed_bytes(&elements).map_err(de::Error::custom)
             }
 
             fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                bytes_to_blst_p2(v).map_err(de::Error::custom)
+                G2Point::from_compressed_bytes(v).map_err(de::Error::custom)
             }
 
             fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                bytes_to_blst_p2(v).map_err(de::Error::custom)
+                G2Point::from_compressed_bytes(v).map_err(de::Error::custom)
             }
         }
 
@@ -342,14 +473,196 @@ impl<'de> Deserialize<'de> for G2Point {
     }
 }
 
-fn blst_scalar_from_i128_as_abs(a: i128) -> blst::blst_scalar {
-    let mut padded_bytes = [0u8; 48];
-    padded_bytes[..16].copy_from_slice(&a.unsigned_abs().to_le_bytes());
-    let mut scalar: blst::blst_scalar = blst::blst_scalar::default();
-    unsafe {
-        blst::blst_scalar_from_le_bytes(&mut scalar, padded_bytes.as_ptr(), padded_bytes.len())
-    };
-    scalar
+/// Precomputed table of the G1 generator, shared by all projections of scalars on G1
+static G1_GENERATOR_TABLE: LazyLock<FixedBaseTable<G1Point>> =
+    LazyLock::new(|| FixedBaseTable::new(&G1Point::generator()));
+
+/// Precomputed table of the G2 generator, shared by all projections of scalars on G2
+static G2_GENERATOR_TABLE: LazyLock<FixedBaseTable<G2Point>> =
+    LazyLock::new(|| FixedBaseTable::new(&G2Point::generator()));
+
+/// Number of bits of the scalar handled by one window of a fixed-base table
+const FIXED_BASE_WINDOW_BITS: usize = 5;
+/// Number of windows needed to cover a 256-bit scalar
+const FIXED_BASE_WINDOWS_COUNT: usize = 256usize.div_ceil(FIXED_BASE_WINDOW_BITS);
+/// Number of digits of a window, zero included
+const FIXED_BASE_WINDOW_SIZE: usize = 1 << FIXED_BASE_WINDOW_BITS;
+
+/// Point of a group that can be multiplied with a fixed-base table
+pub trait FixedBasePoint: Clone {
+    /// Point in affine coordinates, whose default value is the point at infinity
+    type Affine: Clone + Default + std::fmt::Debug;
+
+    /// Returns the point at infinity
+    fn identity() -> Self;
+
+    /// Returns the sum of two points, also valid for equal points and the point at infinity
+    ///
+    /// * `other` - Point to add to self
+    fn add_or_double(&self, other: &Self) -> Self;
+
+    /// Returns the sum of self and a point in affine coordinates, also valid for equal points and the point at infinity
+    ///
+    /// * `other` - Point to add to self
+    fn add_or_double_affine(&self, other: &Self::Affine) -> Self;
+
+    /// Returns the point in affine coordinates
+    fn to_affine(&self) -> Self::Affine;
+
+    /// Replaces a point by another one if the mask is all ones and keeps it if the mask is zero, without branching
+    ///
+    /// * `point` - Point to update
+    /// * `other` - Point to assign
+    /// * `mask` - Either `0` or `u64::MAX`
+    fn conditional_assign(point: &mut Self::Affine, other: &Self::Affine, mask: u64);
+}
+
+impl FixedBasePoint for G1Point {
+    type Affine = blst::blst_p1_affine;
+
+    fn identity() -> Self {
+        blst::blst_p1::default().into()
+    }
+
+    fn add_or_double(&self, other: &Self) -> Self {
+        self.add(other)
+    }
+
+    fn add_or_double_affine(&self, other: &Self::Affine) -> Self {
+        let mut out = blst::blst_p1::default();
+        unsafe {
+            blst::blst_p1_add_or_double_affine(&mut out, self.as_raw_ptr(), other);
+        };
+        out.into()
+    }
+
+    fn to_affine(&self) -> Self::Affine {
+        let mut out = blst::blst_p1_affine::default();
+        unsafe {
+            blst::blst_p1_to_affine(&mut out, self.as_raw_ptr());
+        };
+        out
+    }
+
+    fn conditional_assign(point: &mut Self::Affine, other: &Self::Affine, mask: u64) {
+        conditional_assign_fp(&mut point.x, &other.x, mask);
+        conditional_assign_fp(&mut point.y, &other.y, mask);
+    }
+}
+
+impl FixedBasePoint for G2Point {
+    type Affine = blst::blst_p2_affine;
+
+    fn identity() -> Self {
+        blst::blst_p2::default().into()
+    }
+
+    fn add_or_double(&self, other: &Self) -> Self {
+        self.add(other)
+    }
+
+    fn add_or_double_affine(&self, other: &Self::Affine) -> Self {
+        let mut out = blst::blst_p2::default();
+        unsafe {
+            blst::blst_p2_add_or_double_affine(&mut out, self.as_raw_ptr(), other);
+        };
+        out.into()
+    }
+
+    fn to_affine(&self) -> Self::Affine {
+        let mut out = blst::blst_p2_affine::default();
+        unsafe {
+            blst::blst_p2_to_affine(&mut out, self.as_raw_ptr());
+        };
+        out
+    }
+
+    fn conditional_assign(point: &mut Self::Affine, other: &Self::Affine, mask: u64) {
+        for (a, b) in point
+            .x
+            .fp
+            .iter_mut()
+            .chain(&mut point.y.fp)
+            .zip(other.x.fp.iter().chain(&other.y.fp))
+        {
+            conditional_assign_fp(a, b, mask);
+        }
+    }
+}
+
+fn conditional_assign_fp(a: &mut blst::blst_fp, b: &blst::blst_fp, mask: u64) {
+    for (a, b) in a.l.iter_mut().zip(b.l) {
+        *a ^= mask & (*a ^ b);
+    }
+}
+
+/// Returns `u64::MAX` if both values are equal and `0` otherwise, without branching
+fn equality_mask(a: u64, b: u64) -> u64 {
+    let difference = std::hint::black_box(a ^ b);
+    // The top bit of `d | -d` is set if and only if `d` is not zero
+    ((difference | difference.wrapping_neg()) >> 63).wrapping_sub(1)
+}
+
+/// Returns the digit of a scalar in a window, i.e. its bits `w i` to `w (i + 1) - 1`
+///
+/// * `le_bytes` - Scalar in little endian bytes
+/// * `index` - Index `i` of the window
+fn window_digit(le_bytes: &[u8; 32], index: usize) -> u64 {
+    let bit = index * FIXED_BASE_WINDOW_BITS;
+    let low = le_bytes[bit / 8] as u64;
+    let high = le_bytes.get(bit / 8 + 1).copied().unwrap_or_default() as u64;
+    ((low | high << 8) >> (bit % 8)) & ((1 << FIXED_BASE_WINDOW_BITS) - 1)
+}
+
+/// Windowed precomputation table for multiplications of a fixed point of G1 or G2.
+///
+/// For each window `i` of 5 bits of the scalar, the table holds the multiples `j * 2^(5 * i) * base` for
+/// `j = 0, ..., 31` in affine coordinates. A multiplication is then reduced to 52 mixed point additions.
+///
+/// The multiplication is constant-time with respect to the scalar, as it is used with secrets such as the powers of
+/// the trusted setup: every window is added, zero digits included, and the multiple of each window is selected by
+/// scanning the whole window with masks instead of indexing it by the digit.
+#[derive(Debug)]
+pub struct FixedBaseTable<P: FixedBasePoint> {
+    windows: Vec<Vec<P::Affine>>,
+}
+
+impl<P: FixedBasePoint> FixedBaseTable<P> {
+    /// Precomputes the windows for a base point
+    ///
+    /// * `base` - Fixed point that will be multiplied
+    pub fn new(base: &P) -> Self {
+        let mut windows = Vec::with_capacity(FIXED_BASE_WINDOWS_COUNT);
+        let mut window_base = base.clone();
+        for _ in 0..FIXED_BASE_WINDOWS_COUNT {
+            let mut multiples = Vec::with_capacity(FIXED_BASE_WINDOW_SIZE);
+            multiples.push(P::identity());
+            for j in 1..FIXED_BASE_WINDOW_SIZE {
+                multiples.push(multiples[j - 1].add_or_double(&window_base));
+            }
+            // Base of the next window is 2^5 times the base of the current one
+            window_base = multiples[FIXED_BASE_WINDOW_SIZE - 1].add_or_double(&window_base);
+            windows.push(multiples.iter().map(P::to_affine).collect());
+        }
+        Self { windows }
+    }
+
+    /// Multiply the base point by a scalar using the precomputed windows, in constant time
+    ///
+    /// * `a` - Scalar that will multiply the base point
+    pub fn mult(&self, a: &Scalar) -> P {
+        let le_bytes = a.to_le_bytes();
+        let mut out = P::identity();
+        for (i, window) in self.windows.iter().enumerate() {
+            let digit = window_digit(&le_bytes, i);
+            let mut multiple = P::Affine::default();
+            for (j, candidate) in window.iter().enumerate() {
+                P::conditional_assign(&mut multiple, candidate, equality_mask(j as u64, digit));
+            }
+            out = out.add_or_double_affine(&multiple);
+        }
+        out
+    }
 }
 
 pub fn bilinear_map(p1: &G1Point, p2: &G2Point) -> blst::blst_fp12 {
@@ -370,8 +683,87 @@ pub fn bilinear_map(p1: &G1Point, p2: &G2Point) -> blst::blst_fp12 {
     res
 }
 
+/// Computes the product of the pairings `e(p1_i, p2_i)`, sharing a single final exponentiation
+///
+/// * `pairs` - Pairs of points of both groups
+pub fn multi_pairing(pairs: &[(&G1Point, &G2Point)]) -> blst::blst_fp12 {
+    let mut res = unsafe { *blst::blst_fp12_one() };
+    for (p1, p2) in pairs {
+        let mut p1_affine = blst::blst_p1_affine::default();
+        let mut p2_affine = blst::blst_p2_affine::default();
+        let mut miller_loop = blst::blst_fp12::default();
+        unsafe {
+            blst::blst_p1_to_affine(&mut p1_affine, p1.as_raw_ptr());
+            blst::blst_p2_to_affine(&mut p2_affine, p2.as_raw_ptr());
+            blst::blst_miller_loop(&mut miller_loop, &p2_affine, &p1_affine);
+            blst::blst_fp12_mul(&mut res, &res, &miller_loop);
+        };
+    }
+    unsafe {
+        blst::blst_final_exp(&mut res, &res);
+    };
+    res
+}
+
+/// Checks that the product of the pairings `e(p1_i, p2_i)` is the identity of the target group
+///
+/// * `pairs` - Pairs of points of both groups
+pub fn multi_pairing_is_one(pairs: &[(&G1Point, &G2Point)]) -> bool {
+    unsafe { blst::blst_fp12_is_one(&multi_pairing(pairs)) }
+}
+
 #[cfg(test)]
 mod tests {
+    use fake::{Fake, Faker};
+
+    use super::*;
+
+    #[test]
+    fn test_generator_table_multiplication() {
+        for _ in 0..10 {
+            let a: [u8; 32] = Faker.fake();
+            let scalar = Scalar::from_le_bytes(a);
+
+            assert_eq!(
+                G1Point::from_scalar(scalar.clone()),
+                G1Point::generator().mult(&scalar),
+                "table and double-and-add multiplications must agree on G1"
+            );
+
+            let scalar_le_bytes = scalar.to_le_bytes();
+            let mut expected_g2 = blst::blst_p2::default();
+            unsafe {
+                blst::blst_p2_mult(
+                    &mut expected_g2,
+                    blst::blst_p2_generator(),
+                    scalar_le_bytes.as_ptr(),
+                    256,
+                );
+            };
+            assert_eq!(
+                G2Point::from_scalar(scalar),
+                expected_g2.into(),
+                "table and double-and-add multiplications must agree on G2"
+            );
+        }
+
+        // Zero digits are added as the point at infinity, for any base
+        let table = FixedBaseTable::new(&G2Point::from_i128(7));
+        for scalar in [0, 1, 0x100, 0xf0f0] {
+            let scalar = Scalar::from_i128(scalar);
+            assert_eq!(table.mult(&scalar), G2Point::from_i128(7).mult(&scalar));
+        }
+        assert_eq!(equality_mask(3, 3), u64::MAX);
+        assert_eq!(equality_mask(3, 4), 0);
+
+        let a: i128 = Faker.fake();
+        assert_eq!(
+            G1Point::from_i128(a).add(&G1Point::from_i128(-a)),
+            G1Point::from_i128(0),
+            "projection of opposite values must add up to the identity"
+        );
+    }
+
     #[test]
     fn test_point_addition_and_scalar_multiplication() {
         unsafe {
@@ -449,4 +841,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_multi_pairing() {
+        let (a, b) = (Scalar::from_i128(6), Scalar::from_i128(7));
+        let g1 = G1Point::generator();
+        let g2 = G2Point::generator();
+
+        // e(6 G1, 7 G2) = e(42 G1, G2)
+        let lhs = G1Point::from_scalar(a.clone());
+        let rhs = G2Point::from_scalar(b.clone());
+        let product = G1Point::from_scalar(a.mul(&b));
+        assert!(
+            multi_pairing(&[(&lhs, &rhs)]) == bilinear_map(&product, &g2),
+            "a single pair must give the pairing"
+        );
+        assert!(
+            multi_pairing_is_one(&[(&lhs, &rhs), (&g1.mult(&Scalar::from_i128(-42)), &g2)]),
+            "opposite pairings must cancel out"
+        );
+        assert!(!multi_pairing_is_one(&[
+            (&lhs, &rhs),
+            (&g1.mult(&Scalar::from_i128(-41)), &g2)
+        ]));
+    }
 }
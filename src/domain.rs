@@ -0,0 +1,205 @@
+use super::{curves::G1Point, scalar::Scalar};
+
+/// Two-adicity of the scalar field: `r - 1 = 2^32 * t` with `t` odd
+const TWO_ADICITY: u32 = 32;
+/// Multiplicative generator of the scalar field
+const MULTIPLICATIVE_GENERATOR: i128 = 7;
+/// Odd factor `t = (r - 1) / 2^32` in big endian hexadecimal
+const T_AS_HEX: &str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff";
+
+/// Multiplicative subgroup of the scalar field made of the `n`-th roots of unity, `n` being a power of two.
+///
+/// The elements of the domain are `1, ω, ω^2, ..., ω^(n - 1)` where `ω` is a primitive `n`-th root of unity.
+#[derive(Debug, Clone)]
+pub struct Domain {
+    size: usize,
+    generator: Scalar,
+    generator_inv: Scalar,
+    size_inv: Scalar,
+}
+
+impl Domain {
+    /// Creates the domain of the `size`-th roots of unity
+    ///
+    /// * `size` - Size of the domain, must be a power of two up to `2^32`
+    pub fn new(size: usize) -> Result<Self, anyhow::Error> {
+        if !size.is_power_of_two() || size.trailing_zeros() > TWO_ADICITY {
+            return Err(anyhow::anyhow!(
+                "Domain size must be a power of two up to 2^{TWO_ADICITY}, got {size}"
+            ));
+        }
+
+        // ω = g^(t * 2^(32 - k)) for a domain of size 2^k
+        let mut t_le_bytes = [0u8; 32];
+        let t_be_bytes = hex::decode(T_AS_HEX)?;
+        for (i, b) in t_be_bytes.iter().rev().enumerate() {
+            t_le_bytes[i] = *b;
+        }
+        let mut generator = Scalar::from_i128(MULTIPLICATIVE_GENERATOR).pow_le_bytes(&t_le_bytes);
+        for _ in size.trailing_zeros()..TWO_ADICITY {
+            generator = generator.mul(&generator);
+        }
+
+        Ok(Self {
+            size,
+            generator_inv: generator.inv()?,
+            generator,
+            size_inv: Scalar::from_i128(size as i128).inv()?,
+        })
+    }
+
+    /// Returns the size of the domain
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the primitive root of unity `ω` generating the domain
+    pub fn generator(&self) -> &Scalar {
+        &self.generator
+    }
+
+    /// Returns the elements `1, ω, ..., ω^(n - 1)` of the domain
+    pub fn elements(&self) -> Vec<Scalar> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut current = Scalar::from_i128(1);
+        for _ in 0..self.size {
+            let next = current.mul(&self.generator);
+            elements.push(current);
+            current = next;
+        }
+        elements
+    }
+
+    /// Fast Fourier transform: evaluates the polynomial of coefficients `values` over the domain
+    ///
+    /// * `values` - Coefficients in ascending degree, there must be exactly `size` of them
+    pub fn fft<T: FftElement>(&self, values: &[T]) -> Result<Vec<T>, anyhow::Error> {
+        self.check_length(values.len())?;
+        let mut out = values.to_vec();
+        fft_in_place(&mut out, &self.generator);
+        Ok(out)
+    }
+
+    /// Inverse fast Fourier transform: interpolates the coefficients of the polynomial taking the `values` over the domain
+    ///
+    /// * `values` - Evaluations at `1, ω, ..., ω^(n - 1)`, there must be exactly `size` of them
+    pub fn ifft<T: FftElement>(&self, values: &[T]) -> Result<Vec<T>, anyhow::Error> {
+        self.check_length(values.len())?;
+        let mut out = values.to_vec();
+        fft_in_place(&mut out, &self.generator_inv);
+        Ok(out.iter().map(|v| v.fft_mul(&self.size_inv)).collect())
+    }
+
+    fn check_length(&self, length: usize) -> Result<(), anyhow::Error> {
+        if length != self.size {
+            return Err(anyhow::anyhow!(
+                "Invalid number of values, expected {}, got {length}",
+                self.size
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Elements that can be transformed by a fast Fourier transform over the scalar field
+pub trait FftElement: Clone {
+    fn fft_add(&self, other: &Self) -> Self;
+    fn fft_sub(&self, other: &Self) -> Self;
+    fn fft_mul(&self, scalar: &Scalar) -> Self;
+}
+
+impl FftElement for Scalar {
+    fn fft_add(&self, other: &Self) -> Self {
+        self.add(other)
+    }
+    fn fft_sub(&self, other: &Self) -> Self {
+        self.sub(other)
+    }
+    fn fft_mul(&self, scalar: &Scalar) -> Self {
+        self.mul(scalar)
+    }
+}
+
+impl FftElement for G1Point {
+    fn fft_add(&self, other: &Self) -> Self {
+        self.add(other)
+    }
+    fn fft_sub(&self, other: &Self) -> Self {
+        self.sub(other)
+    }
+    fn fft_mul(&self, scalar: &Scalar) -> Self {
+        self.mult(scalar)
+    }
+}
+
+/// Iterative radix-2 Cooley-Tukey transform, `root` must be a primitive root of unity of order `values.len()`
+fn fft_in_place<T: FftElement>(values: &mut [T], root: &Scalar) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half_size = 1;
+    while half_size < n {
+        let step_root = root.pow(n / (2 * half_size));
+        for start in (0..n).step_by(2 * half_size) {
+            let mut twiddle = Scalar::from_i128(1);
+            for j in start..(start + half_size) {
+                let t = values[j + half_size].fft_mul(&twiddle);
+                let u = values[j].clone();
+                values[j] = u.fft_add(&t);
+                values[j + half_size] = u.fft_sub(&t);
+                twiddle = twiddle.mul(&step_root);
+            }
+        }
+        half_size *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+
+    use super::*;
+    use crate::polynomial::Polynomial;
+
+    #[test]
+    fn test_domain_generator_is_primitive_root_of_unity() {
+        for log_size in [0, 1, 5, 32] {
+            let size = 1usize << log_size;
+            let domain = Domain::new(size).unwrap();
+            let one = Scalar::from_i128(1);
+            let mut powered = domain.generator().clone();
+            for _ in 0..log_size {
+                assert_ne!(powered, one, "generator order must be exactly {size}");
+                powered = powered.mul(&powered);
+            }
+            assert_eq!(powered, one, "generator must be a {size}-th root of unity");
+        }
+        assert!(Domain::new(12).is_err());
+    }
+
+    #[test]
+    fn test_fft_matches_evaluation_and_ifft_inverts_it() {
+        let domain = Domain::new(16).unwrap();
+        let coefficients: Vec<Scalar> = (0..16)
+            .map(|_| Scalar::from_i128(Faker.fake::<i128>()))
+            .collect();
+        let polynomial = Polynomial::try_from(coefficients.clone()).unwrap();
+
+        let evaluations = domain.fft(&coefficients).unwrap();
+        for (x, evaluation) in domain.elements().into_iter().zip(&evaluations) {
+            assert_eq!(&polynomial.evaluate(x).unwrap().result, evaluation);
+        }
+
+        assert_eq!(domain.ifft(&evaluations).unwrap(), coefficients);
+    }
+}
@@ -0,0 +1,521 @@
+//! Inspection of the artifacts written by the CLI.
+//!
+//! The kind of an artifact is detected from the fields of its JSON content. The points are decoded without
+//! validation so that invalid points are reported instead of making the whole artifact unreadable. Points that can
+//! not be decoded, e.g. malformed hexadecimal or a wrong length, are reported apart from the points that are decoded
+//! but not on the curve or not in their subgroup.
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    curves::{G1Point, G2Point, PointDecodingError},
+    polynomial::{Evaluation, Polynomial},
+    trusted_setup::Setup,
+};
+
+/// Maximum number of points of a setup that are individually listed in the text summary
+const DISPLAYED_SETUP_POINTS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    Setup,
+    VerifierKey,
+    Commitment,
+    Evaluation,
+    WorkspaceIndex,
+    /// Result of the `prove` command printed with `--output json`
+    ProofBundle,
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let displayed = match self {
+            ArtifactKind::Setup => "setup",
+            ArtifactKind::VerifierKey => "verifier key",
+            ArtifactKind::Commitment => "commitment",
+            ArtifactKind::Evaluation => "evaluation",
+            ArtifactKind::WorkspaceIndex => "workspace index",
+            ArtifactKind::ProofBundle => "proof bundle",
+        };
+        write!(f, "{displayed}")
+    }
+}
+
+/// Encoding and validation status of a point of an artifact
+#[derive(Debug, Clone, Serialize)]
+pub struct PointReport {
+    pub label: String,
+    pub group: &'static str,
+    /// Compressed point in hexadecimal, as found in the artifact
+    pub hex: String,
+    /// Reason why the point could not be decoded, the curve and subgroup checks are then not performed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
+    pub on_curve: bool,
+    pub in_subgroup: bool,
+}
+
+impl PointReport {
+    pub fn is_valid(&self) -> bool {
+        self.decode_error.is_none() && self.on_curve && self.in_subgroup
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InspectionReport {
+    pub kind: ArtifactKind,
+    /// Human readable properties of the artifact, e.g. degree, polynomial or setup hash
+    pub fields: Vec<(String, String)>,
+    pub points: Vec<PointReport>,
+}
+
+impl InspectionReport {
+    /// Returns true if all the points are decoded, on their curve and in their subgroup
+    pub fn is_valid(&self) -> bool {
+        self.points.iter().all(PointReport::is_valid)
+    }
+}
+
+impl std::fmt::Display for InspectionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Kind: {}", self.kind)?;
+        for (name, value) in &self.fields {
+            writeln!(f, "{name}: {value}")?;
+        }
+
+        let is_setup = self.kind == ArtifactKind::Setup;
+        for (i, point) in self.points.iter().enumerate() {
+            // Setups hold too many points to be listed, only the first ones and the invalid ones are displayed
+            if is_setup && i >= DISPLAYED_SETUP_POINTS && point.is_valid() {
+                continue;
+            }
+            match &point.decode_error {
+                Some(error) => writeln!(
+                    f,
+                    "{} ({}): 0x{} [decoding failed: {error}]",
+                    point.label, point.group, point.hex
+                )?,
+                None => writeln!(
+                    f,
+                    "{} ({}): 0x{} [on curve: {}, in subgroup: {}]",
+                    point.label,
+                    point.group,
+                    point.hex,
+                    yes_no(point.on_curve),
+                    yes_no(point.in_subgroup)
+                )?,
+            }
+        }
+
+        let invalid_count = self.points.iter().filter(|p| !p.is_valid()).count();
+        let undecoded_count = self
+            .points
+            .iter()
+            .filter(|p| p.decode_error.is_some())
+            .count();
+        if invalid_count == 0 {
+            write!(
+                f,
+                "Validation: all {} points are on the curve and in their subgroup",
+                self.points.len()
+            )
+        } else {
+            write!(
+                f,
+                "Validation: {invalid_count} of {} points are invalid, {undecoded_count} of them can not be decoded",
+                self.points.len()
+            )
+        }
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Detects the kind of an artifact and summarizes it
+///
+/// * `content` - JSON content of the artifact
+pub fn inspect(content: &[u8]) -> Result<InspectionReport, anyhow::Error> {
+    let value: Value = serde_json::from_slice(content)
+        .map_err(|e| anyhow::anyhow!("The file is not a JSON artifact: {e}"))?;
+    let has = |key: &str| value.get(key).is_some();
+
+    if value.get("command").and_then(Value::as_str) == Some("prove") {
+        inspect_proof_bundle(&value)
+    } else if has("metadata") && has("artifacts") {
+        inspect_setup(&value)
+    } else if has("setup_hash") && has("s_g2") {
+        inspect_verifier_key(&value)
+    } else if has("setup_hash") && has("polynomial") && has("commitment") {
+        inspect_commitment(&value)
+    } else if has("setup_hash") && has("commitment") && has("openings") {
+        inspect_evaluation(&value)
+    } else if has("commitments") {
+        inspect_workspace_index(&value)
+    } else {
+        Err(anyhow::anyhow!(
+            "Unknown artifact, expected a setup, a verifier key, a commitment, an evaluation, a workspace index or a proof bundle"
+        ))
+    }
+}
+
+fn inspect_setup(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    let metadata = &value["metadata"];
+    let mut fields = vec![];
+    for key in [
+        "degree",
+        "g2_count",
+        "created_at",
+        "tool_version",
+        "insecure",
+    ] {
+        fields.push((key.to_owned(), metadata[key].to_string()));
+    }
+    fields.push((
+        "generation_mode".to_owned(),
+        metadata["generation_mode"].to_string(),
+    ));
+    fields.push((
+        "setup_hash".to_owned(),
+        string_field(metadata, "transcript_hash")?,
+    ));
+
+    let artifacts = value["artifacts"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Invalid setup, the artifacts must be an array"))?;
+    let mut points = vec![];
+    for (i, artifact) in artifacts.iter().enumerate() {
+        points.push(g1_report(format!("s^{i} * G1"), &artifact["g1"]));
+        points.push(g2_report(format!("s^{i} * G2"), &artifact["g2"]));
+    }
+    if let Some(hiding_g1) = value["hiding_g1"].as_array() {
+        fields.push(("hiding_points".to_owned(), hiding_g1.len().to_string()));
+        for (i, point) in hiding_g1.iter().enumerate() {
+            points.push(g1_report(format!("γ s^{i} * G1"), point));
+        }
+    }
+
+    // The metadata can only be checked once all the points are valid
+    let consistency = match serde_json::from_value::<Setup>(value.clone()) {
+        Ok(setup) => match setup.validate() {
+            Ok(()) => "the metadata match the points".to_owned(),
+            Err(e) => format!("invalid, {e}"),
+        },
+        Err(e) => format!("unable to read the setup, {e}"),
+    };
+    fields.push(("consistency".to_owned(), consistency));
+
+    Ok(InspectionReport {
+        kind: ArtifactKind::Setup,
+        fields,
+        points,
+    })
+}
+
+fn inspect_verifier_key(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    Ok(InspectionReport {
+        kind: ArtifactKind::VerifierKey,
+        fields: vec![("setup_hash".to_owned(), string_field(value, "setup_hash")?)],
+        points: vec![
+            g1_report("G1".to_owned(), &value["g1"]),
+            g2_report("G2".to_owned(), &value["g2"]),
+            g2_report("s * G2".to_owned(), &value["s_g2"]),
+        ],
+    })
+}
+
+fn inspect_commitment(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    let polynomial: Polynomial = serde_json::from_value(value["polynomial"].clone())
+        .map_err(|e| anyhow::anyhow!("Invalid polynomial in the commitment: {e}"))?;
+    let mut fields = vec![
+        ("setup_hash".to_owned(), string_field(value, "setup_hash")?),
+        ("degree".to_owned(), polynomial.degree().to_string()),
+        ("polynomial".to_owned(), format!("P(x) = {polynomial}")),
+    ];
+    let mut points = vec![g1_report("commitment".to_owned(), &value["commitment"])];
+
+    if let Some(degree_bound) = value.get("degree_bound") {
+        let bound = degree_bound["bound"]
+            .as_u64()
+            .ok_or_else(|| anyhow::anyhow!("Invalid bound in the degree bound proof"))?;
+        fields.push(("degree_bound".to_owned(), format!("deg P <= {bound}")));
+        points.push(g1_report(
+            "proof of the degree bound".to_owned(),
+            &degree_bound["proof"],
+        ));
+    }
+
+    Ok(InspectionReport {
+        kind: ArtifactKind::Commitment,
+        fields,
+        points,
+    })
+}
+
+fn inspect_evaluation(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    let mut fields = vec![("setup_hash".to_owned(), string_field(value, "setup_hash")?)];
+    let mut points = vec![g1_report("commitment".to_owned(), &value["commitment"])];
+
+    let empty = vec![];
+    let openings = value["openings"].as_array().unwrap_or(&empty);
+    for (i, opening) in openings.iter().enumerate() {
+        let evaluation: Evaluation = serde_json::from_value(opening["evaluation"].clone())
+            .map_err(|e| anyhow::anyhow!("Invalid evaluation in opening {i}: {e}"))?;
+        fields.push((
+            format!("opening {i}"),
+            format!("P({}) = {}", evaluation.point, evaluation.result),
+        ));
+        points.push(g1_report(
+            format!("proof of opening {i}"),
+            &opening["proof"],
+        ));
+    }
+
+    let aggregated_openings = value["aggregated_openings"].as_array().unwrap_or(&empty);
+    for (i, opening) in aggregated_openings.iter().enumerate() {
+        let evaluations: Vec<Evaluation> =
+            serde_json::from_value(opening["evaluation"]["evaluations"].clone()).map_err(|e| {
+                anyhow::anyhow!("Invalid evaluations in aggregated opening {i}: {e}")
+            })?;
+        fields.push((
+            format!("aggregated opening {i}"),
+            evaluations
+                .iter()
+                .map(|e| format!("P({}) = {}", e.point, e.result))
+                .collect::<Vec<String>>()
+                .join(", "),
+        ));
+        points.push(g1_report(
+            format!("proof of aggregated opening {i}"),
+            &opening["proof"],
+        ));
+    }
+
+    Ok(InspectionReport {
+        kind: ArtifactKind::Evaluation,
+        fields,
+        points,
+    })
+}
+
+fn inspect_proof_bundle(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    let mut fields = vec![];
+    for key in ["setup_hash", "polynomial", "x", "y"] {
+        fields.push((key.to_owned(), string_field(value, key)?));
+    }
+    Ok(InspectionReport {
+        kind: ArtifactKind::ProofBundle,
+        fields,
+        points: vec![
+            g1_report("commitment".to_owned(), &value["commitment"]),
+            g1_report("proof".to_owned(), &value["proof"]),
+        ],
+    })
+}
+
+fn inspect_workspace_index(value: &Value) -> Result<InspectionReport, anyhow::Error> {
+    let commitments = value["commitments"]
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Invalid workspace index, expected named commitments"))?;
+    let mut fields = vec![];
+    let mut points = vec![];
+    for (name, entry) in commitments {
+        fields.push((
+            format!("\"{name}\""),
+            format!(
+                "degree {}, setup {}, {}",
+                entry["degree"],
+                string_field(entry, "setup_hash")?,
+                if entry["evaluation_path"].is_null() {
+                    "not evaluated"
+                } else {
+                    "evaluated"
+                }
+            ),
+        ));
+        points.push(g1_report(
+            format!("commitment \"{name}\""),
+            &entry["commitment"],
+        ));
+    }
+    Ok(InspectionReport {
+        kind: ArtifactKind::WorkspaceIndex,
+        fields,
+        points,
+    })
+}
+
+fn string_field(value: &Value, key: &str) -> Result<String, anyhow::Error> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow::anyhow!("Missing field \"{key}\""))
+}
+
+/// Reads the bytes of a point, either an array of bytes or a hexadecimal string
+fn point_bytes(value: &Value) -> Result<Vec<u8>, String> {
+    match value {
+        Value::Array(bytes) => bytes
+            .iter()
+            .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| "Invalid array of bytes".to_owned()),
+        Value::String(s) => hex::decode(s.trim_start_matches("0x"))
+            .map_err(|e| format!("Invalid hexadecimal encoding, {e}")),
+        Value::Null => Err("Missing point".to_owned()),
+        _ => Err("Expected an array of bytes or a hexadecimal string".to_owned()),
+    }
+}
+
+/// Decodes a point and checks it
+///
+/// * `label` - Name of the point in the artifact
+/// * `group` - Name of the group of the point
+/// * `value` - Encoded point
+/// * `decode` - Decodes the compressed point and returns whether it is in its subgroup
+fn point_report(
+    label: String,
+    group: &'static str,
+    value: &Value,
+    decode: impl Fn(&[u8]) -> Result<bool, PointDecodingError>,
+) -> PointReport {
+    let (hex, status) = match point_bytes(value) {
+        Ok(bytes) => (hex::encode(&bytes), decode(&bytes)),
+        Err(e) => {
+            return PointReport {
+                label,
+                group,
+                hex: value.as_str().unwrap_or_default().to_owned(),
+                decode_error: Some(e),
+                on_curve: false,
+                in_subgroup: false,
+            };
+        }
+    };
+    let (decode_error, on_curve, in_subgroup) = match status {
+        Ok(in_subgroup) => (None, true, in_subgroup),
+        Err(PointDecodingError::NotOnCurve) => (None, false, false),
+        Err(e) => (Some(e.to_string()), false, false),
+    };
+    PointReport {
+        label,
+        group,
+        hex,
+        decode_error,
+        on_curve,
+        in_subgroup,
+    }
+}
+
+fn g1_report(label: String, value: &Value) -> PointReport {
+    point_report(label, "G1", value, |bytes| {
+        G1Point::decode_compressed_bytes(bytes).map(|p| p.is_in_group())
+    })
+}
+
+fn g2_report(label: String, value: &Value) -> PointReport {
+    point_report(label, "G2", value, |bytes| {
+        G2Point::decode_compressed_bytes(bytes).map(|p| p.is_in_group())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        artifacts::{CommitmentArtifact, VerifierKeyArtifact},
+        trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator},
+    };
+
+    #[test]
+    fn test_inspect_setup_and_commitment() {
+        let setup = Setup::new(
+            ParallelSetupArtifactsGenerator::new([3; 32]).generate(3),
+            GenerationMode::RandomSecret,
+        )
+        .unwrap();
+        let report = inspect(&serde_json::to_vec(&setup).unwrap()).unwrap();
+        assert_eq!(report.kind, ArtifactKind::Setup);
+        assert_eq!(report.points.len(), 6);
+        assert!(report.is_valid());
+
+        let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+        let commitment = polynomial.commit(&setup.artifacts).unwrap();
+        let mut artifact = serde_json::to_value(CommitmentArtifact {
+            setup_hash: setup.hash().to_owned(),
+            polynomial,
+            commitment,
+            degree_bound: None,
+        })
+        .unwrap();
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert_eq!(report.kind, ArtifactKind::Commitment);
+        assert!(report.is_valid());
+        assert!(
+            report
+                .fields
+                .contains(&("polynomial".to_owned(), "P(x) = 3x^2 + 2x + 1".to_owned()))
+        );
+
+        // Points of the curve are almost never in the G1 subgroup, the first one found with a small x is used
+        let outside_subgroup = (0u8..=255)
+            .map(|x| {
+                let mut compressed = vec![0u8; 48];
+                compressed[0] = 0x80;
+                compressed[47] = x;
+                compressed
+            })
+            .find(|compressed| G1Point::from_compressed_bytes(compressed).is_ok())
+            .unwrap();
+        artifact["commitment"] = serde_json::to_value(outside_subgroup).unwrap();
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert!(report.points[0].on_curve);
+        assert!(!report.points[0].in_subgroup);
+
+        // Coordinates x for which x^3 + 4 is not a square are field elements but not on the curve
+        let off_curve = (0u8..=255)
+            .map(|x| {
+                let mut compressed = vec![0u8; 48];
+                compressed[0] = 0x80;
+                compressed[47] = x;
+                compressed
+            })
+            .find(|compressed| {
+                G1Point::decode_compressed_bytes(compressed).err()
+                    == Some(PointDecodingError::NotOnCurve)
+            })
+            .unwrap();
+        artifact["commitment"] = serde_json::to_value(off_curve).unwrap();
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert_eq!(report.points[0].decode_error, None);
+        assert!(!report.points[0].on_curve);
+        assert!(!report.is_valid());
+
+        // Malformed encodings are decoding errors, not points off the curve
+        artifact["commitment"] = serde_json::to_value(vec![0u8; 48]).unwrap();
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert!(report.points[0].decode_error.is_some());
+        artifact["commitment"] = serde_json::to_value(vec![0u8; 47]).unwrap();
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert_eq!(
+            report.points[0].decode_error.as_deref(),
+            Some("Invalid length, expected 48, got 47")
+        );
+        artifact["commitment"] = Value::String("0xnot hex".to_owned());
+        let report = inspect(artifact.to_string().as_bytes()).unwrap();
+        assert!(report.points[0].decode_error.is_some());
+        assert!(!report.is_valid());
+
+        let verifier_key = VerifierKeyArtifact::from_setup(&setup).unwrap();
+        let report = inspect(&serde_json::to_vec(&verifier_key).unwrap()).unwrap();
+        assert_eq!(report.kind, ArtifactKind::VerifierKey);
+        assert_eq!(report.points.len(), 3);
+        assert!(report.is_valid());
+
+        assert!(inspect(b"{\"unknown\": 1}").is_err());
+        assert!(inspect(b"not json").is_err());
+    }
+}
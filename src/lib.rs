@@ -1,12 +1,20 @@
+pub mod artifacts;
 pub mod curves;
+pub mod domain;
+pub mod inspect;
 pub mod polynomial;
+pub mod protocol;
+pub mod repl;
 pub mod scalar;
+pub mod server;
+pub mod transcript;
 pub mod trusted_setup;
+pub mod workspace;
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        polynomial::Polynomial,
+        polynomial::{MultiPointEvaluation, Polynomial},
         scalar::Scalar,
         trusted_setup::{SetupArtifact, SetupArtifactsGenerator},
     };
@@ -92,4 +100,40 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_multi_point_proof() {
+        let setup_artifacts = &generate_setup_artifacts(6);
+        let polynomial = generate_polynomial(6);
+        let commitment = polynomial.commit(setup_artifacts).unwrap();
+
+        for point_count in 1..=4 {
+            let points: Vec<Scalar> = (0..point_count)
+                .map(|_| Scalar::from_i128(Faker.fake::<i128>()))
+                .collect();
+            let multi_evaluation = MultiPointEvaluation::new(&polynomial, &points).unwrap();
+            let proof = multi_evaluation
+                .generate_proof(&polynomial, setup_artifacts)
+                .unwrap();
+            assert!(
+                multi_evaluation
+                    .verify_proof(&proof, &commitment, setup_artifacts)
+                    .unwrap()
+            );
+
+            let mut tampered = multi_evaluation.clone();
+            tampered.evaluations[0].result =
+                tampered.evaluations[0].result.add(&Scalar::from_i128(1));
+            assert!(
+                !tampered
+                    .verify_proof(&proof, &commitment, setup_artifacts)
+                    .unwrap()
+            );
+            assert!(
+                tampered
+                    .generate_proof(&polynomial, setup_artifacts)
+                    .is_err()
+            );
+        }
+    }
 }
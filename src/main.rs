@@ -1,56 +1,387 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use rand::RngCore;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use std::{
     fs,
-    io::{BufReader, Write},
+    io::{BufReader, BufWriter, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
     str::FromStr,
 };
 use thiserror::Error;
 
 use kzg_poly_commit_exploration::{
+    artifacts::{
+        AggregatedOpening, CommitmentArtifact, EvaluationArtifact, Opening, VerifierKeyArtifact,
+        check_setup_hash, read_artifact, write_artifact,
+    },
     curves::G1Point,
-    polynomial::{Evaluation, Polynomial},
+    inspect::{self, InspectionReport},
+    polynomial::{
+        Evaluation, MultiPointEvaluation, Polynomial,
+        degree_bound::DegreeBoundProof,
+        file::{self, ScalarsFormat},
+        hiding::HidingEvaluation,
+    },
+    protocol::{self, Channel, Endpoint},
+    repl::Repl,
     scalar::Scalar,
-    trusted_setup,
+    server::{self, Server, ServerOptions},
+    transcript::Transcript,
+    trusted_setup::{self, GenerationMode, Setup},
+    workspace::Workspace,
 };
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    after_help = "Exit codes: 0 on success, 1 if a verification fails, 2 on invalid input, 3 on unexpected errors."
+)]
 struct Cli {
     /// Turn debugging information on
     #[arg(short, long, action = clap::ArgAction::Count)]
     debug: u8,
 
+    /// Folder of the artifacts, used for every artifact path that is not given explicitly
+    #[arg(
+        long,
+        global = true,
+        env = "ARTIFACTS_DIR",
+        default_value = "./artifacts"
+    )]
+    artifacts_dir: PathBuf,
+
+    /// Reject the coefficients and points that are not canonical field elements, i.e. negative values or values larger than or equal to the field modulus r, instead of reducing them modulo r
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Format of the output: logs only, or a structured result printed in JSON on the standard output
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Perform a trusted setup ceremony and write the artifacts in './artifacts/setup.json'.
+    /// Perform a trusted setup ceremony and write the artifacts in '<ARTIFACTS_DIR>/setup.json'.
+    TrustedSetup {
+        /// Maximum degree of the polynomials that can be committed with the setup, at least 1
+        #[arg(long, default_value_t = DEFAULT_DEGREE, value_parser = clap::value_parser!(u32).range(1..))]
+        degree: u32,
+        /// Path of the setup artifacts to write, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Also generate the points `[γ s^i]_1` of the hiding commitments, with a random `γ`
+        #[arg(long)]
+        hiding: bool,
+    },
+    /// Import the trusted setup artifacts from a BLS12-381 `.ptau` file and write them in '<ARTIFACTS_DIR>/setup.json'.
     ///
-    /// Artifacts are genetated until degree 9.
-    TrustedSetup {},
+    /// Artifacts are imported until the requested degree, or less if the file does not contain enough powers.
+    ImportPtau {
+        /// Path to the `.ptau` file, as produced by snarkjs or the Perpetual Powers of Tau ceremonies
+        #[arg()]
+        file: PathBuf,
+        /// Maximum degree of the polynomials that can be committed with the imported setup, at least 1
+        #[arg(long, default_value_t = DEFAULT_DEGREE, value_parser = clap::value_parser!(u32).range(1..))]
+        degree: u32,
+        /// Path of the setup artifacts to write, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Export the trusted setup artifacts in the text format of c-kzg-4844 and go-kzg-4844.
+    ExportSetup {
+        /// Path of the text file to write
+        #[arg()]
+        out: PathBuf,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Number of G2 points to export, all of them by default
+        #[arg(long)]
+        g2_count: Option<usize>,
+        /// Also export the G1 points in Lagrange form, as expected by c-kzg-4844.
+        ///
+        /// The number of artifacts must be a power of two.
+        #[arg(long)]
+        lagrange: bool,
+    },
+    /// Export the points of the trusted setup needed to verify single evaluations
+    ExportVerifierKey {
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Path of the verifier key to write, defaults to '<ARTIFACTS_DIR>/verifier_key.json'
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
     /// Commit to a polynomial using the trusted setup artifacts
     Commit {
-        /// Coefficients of the polynomial in ascending degree, starting from the degree zero.
-        ///
-        /// Degree up to 9 is supported.
-        #[arg(long_help, num_args = 1..)]
-        coefficients: Vec<i128>,
+        #[command(flatten)]
+        polynomial: PolynomialInput,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Path of the commitment artifact to write, defaults to '<ARTIFACTS_DIR>/commitment.json'
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Name of the commitment in the workspace of the artifacts folder
+        #[arg(long, conflicts_with = "out")]
+        name: Option<String>,
+        /// Prove that the degree of the polynomial is lower or equal to this bound, the proof is stored in the commitment artifact
+        #[arg(long)]
+        degree_bound: Option<u32>,
     },
-    /// Evaluate the committed polynomial at an input point and generate the associated Kate proof.
+    /// Evaluate the committed polynomial at input points and generate the associated Kate proofs.
+    ///
+    /// Openings are added to the ones previously stored for the same commitment, replacing the ones at the same points.
     Evaluate {
-        /// Input point
+        /// Input points, decimal values of up to 155 digits or `0x` prefixed hexadecimal values of up to 128 digits, optionally negative, reduced modulo r unless `--strict` is set
+        #[arg(required = true, num_args = 1.., allow_negative_numbers = true)]
+        x: Vec<String>,
+        /// Prove all the evaluations with a single proof instead of one proof per point, the points must be distinct
+        #[arg(long)]
+        aggregate: bool,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Path of the commitment artifact, defaults to '<ARTIFACTS_DIR>/commitment.json'
+        #[arg(long)]
+        commitment: Option<PathBuf>,
+        /// Path of the evaluation artifact to write, defaults to '<ARTIFACTS_DIR>/evaluation.json'
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Name of the commitment to evaluate in the workspace of the artifacts folder
+        #[arg(long, conflicts_with_all = ["commitment", "out"])]
+        name: Option<String>,
+    },
+    /// Verify all the stored openings of the committed polynomial with their proofs
+    VerifyEvaluation {
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Path of the commitment artifact, defaults to '<ARTIFACTS_DIR>/commitment.json'
+        #[arg(long)]
+        commitment: Option<PathBuf>,
+        /// Path of the evaluation artifact, defaults to '<ARTIFACTS_DIR>/evaluation.json'
+        #[arg(long)]
+        evaluation: Option<PathBuf>,
+        /// Name of the commitment to verify in the workspace of the artifacts folder
+        #[arg(long, conflicts_with_all = ["commitment", "evaluation"])]
+        name: Option<String>,
+    },
+    /// List the named commitments of the workspace of the artifacts folder
+    List {},
+    /// Detect the kind of an artifact and print a summary of it, along with the validation of its points.
+    ///
+    /// Setups, verifier keys, commitments, evaluations, workspace indexes and proof bundles printed by `prove --output json` are supported.
+    Inspect {
+        /// Path of the artifact
         #[arg()]
-        x: i128,
+        file: PathBuf,
     },
-    /// Verify the previous evaluation with its proof
-    VerifyEvaluation {},
+    /// Commit to a polynomial and prove its evaluation at a point without writing any artifact.
+    ///
+    /// The commitment, the point, the evaluation and the proof are printed so that they can be sent to a verifier.
+    Prove {
+        #[command(flatten)]
+        polynomial: PolynomialInput,
+        /// Input point, decimal value of up to 155 digits or `0x` prefixed hexadecimal value of up to 128 digits, optionally negative, reduced modulo r unless `--strict` is set
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            required_unless_present = "fiat_shamir"
+        )]
+        x: Option<String>,
+        /// Derive the input point from the setup hash, the degree of the polynomial and the commitment with a Fiat–Shamir transcript
+        #[arg(long, conflicts_with = "x")]
+        fiat_shamir: bool,
+        /// Blind the commitment with a random polynomial, the setup must contain the points of hiding commitments.
+        ///
+        /// The evaluation of the blinding polynomial is printed along with the proof.
+        #[arg(long, long_help)]
+        hiding: bool,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+    },
+    /// Verify the proof of an evaluation given as arguments, without reading any artifact other than the setup
+    Verify {
+        /// Compressed G1 point of the commitment in hexadecimal
+        #[arg(long)]
+        commitment: String,
+        /// Input point, decimal or `0x` prefixed hexadecimal value
+        #[arg(
+            long,
+            allow_negative_numbers = true,
+            required_unless_present = "fiat_shamir"
+        )]
+        x: Option<String>,
+        /// Derive the input point as `prove --fiat-shamir` does, the degree of the polynomial must be given
+        #[arg(long, conflicts_with = "x", requires = "degree")]
+        fiat_shamir: bool,
+        /// Degree of the committed polynomial, used to derive the input point with `--fiat-shamir`
+        #[arg(long, requires = "fiat_shamir")]
+        degree: Option<u32>,
+        /// Evaluation of the blinding polynomial printed by `prove --hiding`, the commitment is then verified as a hiding commitment
+        #[arg(long, allow_negative_numbers = true)]
+        blinding_y: Option<String>,
+        /// Claimed evaluation of the committed polynomial at the input point, decimal or `0x` prefixed hexadecimal value
+        #[arg(long, allow_negative_numbers = true)]
+        y: String,
+        /// Compressed G1 point of the proof in hexadecimal
+        #[arg(long)]
+        proof: String,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+    },
+    /// Start an interactive session to define polynomials, commit to them, open them and verify the openings.
+    ///
+    /// Commands are read from the standard input, type `help` in the session to list them.
+    Repl {
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+    },
+    /// Connect to a verifier, commit to a polynomial and open it at the challenge point sent by the verifier
+    Prover {
+        /// Address of the verifier, `<host>:<port>` for TCP or `unix:<path>` for a Unix socket on Unix platforms
+        #[arg(long)]
+        connect: Endpoint,
+        #[command(flatten)]
+        polynomial: PolynomialInput,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Write the transcript of the exchanged messages in this file
+        #[arg(long)]
+        transcript: Option<PathBuf>,
+    },
+    /// Wait for a prover, challenge it at a random point and verify its opening
+    Verifier {
+        /// Address to listen on, `<host>:<port>` for TCP or `unix:<path>` for a Unix socket on Unix platforms
+        #[arg(long)]
+        listen: Endpoint,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Write the transcript of the exchanged messages in this file
+        #[arg(long)]
+        transcript: Option<PathBuf>,
+    },
+    /// Serve the commit, prove and verify operations over HTTP with JSON requests, the setup is loaded once.
+    ///
+    /// Endpoints: `GET /health`, `GET /setup/info`, `POST /commit`, `POST /prove`, `POST /verify` and `POST /verify-batch`.
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:3000", env = "SERVE_ADDRESS")]
+        address: String,
+        /// Path of the setup artifacts, defaults to '<ARTIFACTS_DIR>/setup.json'
+        #[arg(long)]
+        setup: Option<PathBuf>,
+        /// Maximum size of a request body, in bytes
+        #[arg(long, default_value_t = server::DEFAULT_MAX_BODY_SIZE)]
+        max_body_size: usize,
+    },
+}
+
+/// Polynomial given either by its coefficients on the command line or by a file of values
+#[derive(Args)]
+struct PolynomialInput {
+    /// Coefficients of the polynomial in ascending degree, starting from the degree zero.
+    ///
+    /// Coefficients are decimal values of up to 155 digits or `0x` prefixed hexadecimal values of up to 128 digits, optionally negative, reduced modulo r unless `--strict` is set.
+    /// Negative hexadecimal values must be placed after `--`.
+    ///
+    /// The degree of the polynomial is limited by the degree of the setup.
+    #[arg(
+        long_help,
+        num_args = 1..,
+        allow_negative_numbers = true,
+        required_unless_present_any = ["from_file", "expr"],
+        conflicts_with_all = ["from_file", "expr"]
+    )]
+    coefficients: Vec<String>,
+    /// Expression of the polynomial, e.g. "3x^2 + 2x + 1" or "(x - 1)(x - 2)".
+    ///
+    /// Coefficients are decimal or `0x` prefixed hexadecimal values reduced modulo r, the multiplication sign can be omitted before `x` or a parenthesis.
+    #[arg(long, conflicts_with_all = ["from_file", "evaluation_form"])]
+    expr: Option<String>,
+    /// Read the values of the polynomial from a file, or from the standard input with '-'.
+    ///
+    /// The file is either a JSON array of values, one value per line or the raw concatenation of the values in 32 bytes little endian.
+    #[arg(long)]
+    from_file: Option<PathBuf>,
+    /// Format of the file of values, detected from its content by default
+    #[arg(long, value_enum, requires = "from_file")]
+    format: Option<ValuesFormat>,
+    /// Interpret the values as the evaluations of the polynomial over the roots of unity instead of its coefficients.
+    ///
+    /// The number of values must be a power of two.
+    #[arg(long)]
+    evaluation_form: bool,
+}
+
+impl PolynomialInput {
+    /// Reads the values and builds the polynomial
+    ///
+    /// * `strict` - Whether the non-canonical values must be rejected instead of being reduced modulo r
+    /// * `max_degree` - Maximum degree of an expression, checked before expanding it
+    fn read(self, strict: bool, max_degree: u32) -> Result<Polynomial, CliError> {
+        if let Some(expr) = self.expr {
+            let polynomial = Polynomial::parse_with_max_degree(&expr, max_degree).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid polynomial expression: {e}\n  {expr}\n  {}^",
+                    " ".repeat(e.column - 1)
+                )
+            })?;
+            return Ok(polynomial);
+        }
+        let values = match self.from_file {
+            Some(path) => {
+                let content = if path.as_os_str() == "-" {
+                    let mut content = vec![];
+                    std::io::stdin().read_to_end(&mut content)?;
+                    content
+                } else {
+                    fs::read(&path).map_err(|e| {
+                        anyhow::anyhow!(
+                            "Unable to read the values file \"{}\": {e}",
+                            path.display()
+                        )
+                    })?
+                };
+                file::read_scalars(&content, self.format.map(ScalarsFormat::from), strict)?
+            }
+            None => self
+                .coefficients
+                .iter()
+                .map(|c| parse_scalar(c, strict))
+                .collect::<Result<Vec<Scalar>, _>>()?,
+        };
+        let polynomial = if self.evaluation_form {
+            Polynomial::from_evaluations(values)?
+        } else {
+            Polynomial::try_from(values)?
+        };
+        Ok(polynomial)
+    }
 }
 
-fn main() {
+fn main() -> ExitCode {
+    // Environment is loaded first as it can provide values for the command line arguments
+    if let Err(err) = dotenvy::dotenv()
+        && !err.not_found()
+    {
+        eprintln!("Error while loading .env file: {err}");
+        return ExitCode::from(EXIT_CODE_INVALID_INPUT);
+    }
+
     let cli = Cli::parse();
 
     let default_log_level = match &cli.debug {
@@ -59,12 +390,6 @@ fn main() {
         _ => log::Level::Trace,
     };
 
-    if let Err(err) = dotenvy::dotenv()
-        && !err.not_found()
-    {
-        panic!("Error while loading .env file: {err}")
-    }
-
     let log_level = match std::env::var("LOG_LEVEL").ok() {
         Some(v) => log::Level::from_str(v.as_str()).unwrap_or(default_log_level),
         None => default_log_level,
@@ -74,247 +399,1159 @@ fn main() {
         panic!("Failed to initialize logging, got error: {err}");
     }
 
-    match cli.command {
-        Some(cmd) => {
-            if let Err(e) = cmd.run() {
-                panic!("Command execution failed with error: {e}");
+    let Some(cmd) = cli.command else {
+        log::warn!("No command has been input");
+        return ExitCode::SUCCESS;
+    };
+
+    let (printed, exit_code) = match cmd.run(&cli.artifacts_dir, cli.strict) {
+        Ok(output) => (serde_json::to_value(&output), output.exit_code()),
+        Err(e) => {
+            log::error!("Command execution failed with error: {e}");
+            (
+                serde_json::to_value(ErrorOutput {
+                    error: e.kind(),
+                    message: e.message(),
+                }),
+                e.exit_code(),
+            )
+        }
+    };
+    if cli.output == OutputFormat::Json {
+        match printed {
+            Ok(printed) => println!("{printed}"),
+            Err(e) => {
+                log::error!("Unable to serialize the output of the command: {e}");
+                return ExitCode::from(EXIT_CODE_UNHANDLED_ERROR);
             }
         }
-        None => {
-            log::warn!("No command has been input")
+    }
+
+    ExitCode::from(exit_code)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human readable logs only
+    Text,
+    /// Structured result printed in JSON on the standard output, logs are kept on the standard error
+    Json,
+}
+
+/// Result of a command, printed on the standard output with `--output json`.
+///
+/// Points are compressed and given in hexadecimal, scalars are given in base 10.
+#[derive(Serialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+enum CommandOutput {
+    TrustedSetup {
+        setup_path: PathBuf,
+        setup_hash: String,
+        degree: u32,
+        hiding: bool,
+    },
+    ImportPtau {
+        setup_path: PathBuf,
+        setup_hash: String,
+        degree: u32,
+    },
+    ExportSetup {
+        out: PathBuf,
+        g1_count: usize,
+        g2_count: usize,
+        lagrange: bool,
+    },
+    ExportVerifierKey {
+        verifier_key_path: PathBuf,
+        setup_hash: String,
+    },
+    Commit {
+        commitment_path: PathBuf,
+        name: Option<String>,
+        polynomial: String,
+        degree: u32,
+        commitment: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        degree_bound: Option<DegreeBoundOutput>,
+        setup_hash: String,
+    },
+    Evaluate {
+        evaluation_path: PathBuf,
+        name: Option<String>,
+        polynomial: String,
+        openings: Vec<OpeningOutput>,
+    },
+    VerifyEvaluation {
+        verified: bool,
+        polynomial: String,
+        commitment: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        degree_bound: Option<DegreeBoundOutput>,
+        openings: Vec<OpeningOutput>,
+    },
+    List {
+        commitments: Vec<ListedCommitment>,
+    },
+    Inspect {
+        valid: bool,
+        #[serde(flatten)]
+        report: InspectionReport,
+    },
+    Prove {
+        polynomial: String,
+        degree: u32,
+        fiat_shamir: bool,
+        commitment: String,
+        x: String,
+        y: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        blinding_y: Option<String>,
+        proof: String,
+        setup_hash: String,
+    },
+    Verify {
+        verified: bool,
+        commitment: String,
+        x: String,
+        y: String,
+        proof: String,
+    },
+    Repl {
+        setup_hash: String,
+    },
+    Serve {
+        address: String,
+        setup_hash: String,
+    },
+    Prover {
+        verified: bool,
+        transcript: Vec<String>,
+    },
+    Verifier {
+        verified: bool,
+        transcript: Vec<String>,
+    },
+}
+
+impl CommandOutput {
+    fn exit_code(&self) -> u8 {
+        match self {
+            CommandOutput::VerifyEvaluation {
+                verified: false, ..
+            }
+            | CommandOutput::Verify {
+                verified: false, ..
+            }
+            | CommandOutput::Inspect { valid: false, .. }
+            | CommandOutput::Prover {
+                verified: false, ..
+            }
+            | CommandOutput::Verifier {
+                verified: false, ..
+            } => EXIT_CODE_VERIFICATION_FAILED,
+            _ => 0,
+        }
+    }
+}
+
+/// Proof of a bound on the degree of a committed polynomial
+#[derive(Serialize)]
+struct DegreeBoundOutput {
+    bound: u32,
+    proof: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verified: Option<bool>,
+}
+
+impl DegreeBoundOutput {
+    fn from_proof(proof: &DegreeBoundProof) -> Self {
+        Self {
+            bound: proof.bound,
+            proof: to_hex(&proof.proof.to_compressed_bytes()),
+            verified: None,
+        }
+    }
+}
+
+/// Opening at one point, or at several points if it is aggregated
+#[derive(Serialize)]
+struct OpeningOutput {
+    aggregated: bool,
+    points: Vec<String>,
+    results: Vec<String>,
+    proof: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verified: Option<bool>,
+}
+
+impl OpeningOutput {
+    fn from_opening(opening: &Opening, verified: Option<bool>) -> Self {
+        Self {
+            aggregated: false,
+            points: vec![opening.evaluation.point.to_string()],
+            results: vec![opening.evaluation.result.to_string()],
+            proof: to_hex(&opening.proof.to_compressed_bytes()),
+            verified,
+        }
+    }
+
+    fn from_aggregated_opening(opening: &AggregatedOpening, verified: Option<bool>) -> Self {
+        let evaluations = &opening.evaluation.evaluations;
+        Self {
+            aggregated: true,
+            points: evaluations.iter().map(|e| e.point.to_string()).collect(),
+            results: evaluations.iter().map(|e| e.result.to_string()).collect(),
+            proof: to_hex(&opening.proof.to_compressed_bytes()),
+            verified,
+        }
+    }
+
+    /// Displays the evaluations as `"P(x_1) = y_1", "P(x_2) = y_2"`
+    fn display_evaluations(&self) -> String {
+        self.points
+            .iter()
+            .zip(&self.results)
+            .map(|(x, y)| format!("\"P({x}) = {y}\""))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[derive(Serialize)]
+struct ListedCommitment {
+    name: String,
+    degree: u32,
+    commitment: String,
+    setup_hash: String,
+    evaluated: bool,
+}
+
+/// Failure of a command, printed on the standard output with `--output json`
+#[derive(Serialize)]
+struct ErrorOutput {
+    error: &'static str,
+    message: String,
+}
+
+/// Displays the points as `"x = x_1", "x = x_2"`
+fn display_points(points: &[Scalar]) -> String {
+    points
+        .iter()
+        .map(|x| format!("\"x = {x}\""))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ValuesFormat {
+    /// JSON array of integers or decimal and hexadecimal strings
+    Json,
+    /// One decimal or hexadecimal value per line
+    Lines,
+    /// Values in 32 bytes little endian, concatenated
+    Binary,
+}
+
+impl From<ValuesFormat> for ScalarsFormat {
+    fn from(value: ValuesFormat) -> Self {
+        match value {
+            ValuesFormat::Json => ScalarsFormat::Json,
+            ValuesFormat::Lines => ScalarsFormat::Lines,
+            ValuesFormat::Binary => ScalarsFormat::Binary,
         }
     }
 }
 
 #[derive(Error, Debug)]
 enum CliError {
+    #[error("Invalid input: {0}")]
+    InvalidInput(anyhow::Error),
     #[error("Unhandled error: {0}")]
-    UnhandledError(#[from] anyhow::Error),
+    UnhandledError(anyhow::Error),
+}
+
+impl CliError {
+    fn kind(&self) -> &'static str {
+        match self {
+            CliError::InvalidInput(_) => "invalid_input",
+            CliError::UnhandledError(_) => "unhandled_error",
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            CliError::InvalidInput(e) | CliError::UnhandledError(e) => format!("{e:#}"),
+        }
+    }
+
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::InvalidInput(_) => EXIT_CODE_INVALID_INPUT,
+            CliError::UnhandledError(_) => EXIT_CODE_UNHANDLED_ERROR,
+        }
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    /// Errors are caused by the input, e.g. malformed values, missing or inconsistent artifacts, except the I/O errors of the system
+    fn from(value: anyhow::Error) -> Self {
+        match value.downcast_ref::<std::io::Error>().map(|e| e.kind()) {
+            Some(
+                std::io::ErrorKind::NotFound
+                | std::io::ErrorKind::InvalidData
+                | std::io::ErrorKind::InvalidInput
+                | std::io::ErrorKind::UnexpectedEof,
+            )
+            | None => CliError::InvalidInput(value),
+            Some(_) => CliError::UnhandledError(value),
+        }
+    }
 }
 
 impl From<std::io::Error> for CliError {
     fn from(value: std::io::Error) -> Self {
-        CliError::UnhandledError(anyhow::Error::new(value))
+        anyhow::Error::new(value).into()
     }
 }
 
-const ARTIFACTS_FOLDER_PATH: &str = "./artifacts";
-const SETUP_ARTIFACTS_PATH: &str = "./artifacts/setup.json";
-const COMMITMENT_ARTIFACTS_PATH: &str = "./artifacts/commitment.json";
-const EVALUATION_ARTIFACTS_PATH: &str = "./artifacts/evaluation.json";
+/// Exit code of a verification that has been performed and failed, or of an inspected artifact with invalid points
+const EXIT_CODE_VERIFICATION_FAILED: u8 = 1;
+/// Exit code of invalid arguments or artifacts, also used by the parser of the command line
+const EXIT_CODE_INVALID_INPUT: u8 = 2;
+/// Exit code of unexpected failures, e.g. the artifacts cannot be written
+const EXIT_CODE_UNHANDLED_ERROR: u8 = 3;
 
-const MAX_DEGREE: u32 = 9;
+const SETUP_ARTIFACTS_FILE_NAME: &str = "setup.json";
+const COMMITMENT_ARTIFACTS_FILE_NAME: &str = "commitment.json";
+const EVALUATION_ARTIFACTS_FILE_NAME: &str = "evaluation.json";
+const VERIFIER_KEY_FILE_NAME: &str = "verifier_key.json";
+
+const DEFAULT_DEGREE: u32 = 9;
+
+/// Label of the Fiat–Shamir transcript of `prove --fiat-shamir`
+const FIAT_SHAMIR_PROTOCOL: &[u8] = b"kzg-poly-commit-exploration/prove/v1";
 
 impl Commands {
-    fn run(self) -> Result<(), CliError> {
-        match self {
-            Commands::TrustedSetup {} => {
-                log::info!("Starting the trusted setup ceremony");
+    fn run(self, artifacts_dir: &Path, strict: bool) -> Result<CommandOutput, CliError> {
+        let artifact_path = |path: Option<PathBuf>, file_name: &str| {
+            path.unwrap_or_else(|| artifacts_dir.join(file_name))
+        };
 
-                if !fs::exists(ARTIFACTS_FOLDER_PATH)? {
-                    fs::create_dir(ARTIFACTS_FOLDER_PATH)?;
-                }
-                if fs::exists(SETUP_ARTIFACTS_PATH)? {
-                    fs::remove_file(SETUP_ARTIFACTS_PATH)?;
-                }
-                let mut file = fs::File::create(SETUP_ARTIFACTS_PATH)?;
+        match self {
+            Commands::TrustedSetup {
+                degree,
+                out,
+                hiding,
+            } => {
+                let out = artifact_path(out, SETUP_ARTIFACTS_FILE_NAME);
+                log::info!("Starting the trusted setup ceremony for degree {degree}");
 
                 let mut s_be_bytes = [0; 32];
                 rand::rng().fill_bytes(&mut s_be_bytes);
 
-                let setup_artifacts: Vec<_> =
-                    trusted_setup::SetupArtifactsGenerator::new(s_be_bytes)
-                        .take((MAX_DEGREE + 1) as usize)
-                        .collect();
-
-                let stringified_artifacts =
-                    serde_json::to_string(&setup_artifacts).map_err(anyhow::Error::from)?;
+                let generator = trusted_setup::ParallelSetupArtifactsGenerator::new(s_be_bytes);
+                let mut setup = Setup::new(
+                    generator.generate(degree as usize + 1),
+                    GenerationMode::RandomSecret,
+                )?;
+                if hiding {
+                    let mut gamma_be_bytes = [0; 32];
+                    rand::rng().fill_bytes(&mut gamma_be_bytes);
+                    setup = setup.with_hiding_powers(
+                        generator.generate_hiding_powers(degree as usize + 1, gamma_be_bytes),
+                    )?;
+                }
 
-                file.write_all(stringified_artifacts.as_bytes())?;
+                write_artifact(&out, &setup)?;
 
                 log::info!(
-                    "Trusted setup ceremony successfully performed. Artifacts have been written in \"{SETUP_ARTIFACTS_PATH}\""
+                    "Trusted setup ceremony successfully performed. Artifacts have been written in \"{}\" with hash {}",
+                    out.display(),
+                    setup.hash()
                 );
 
-                Ok(())
+                Ok(CommandOutput::TrustedSetup {
+                    setup_path: out,
+                    setup_hash: setup.hash().to_owned(),
+                    degree: setup.metadata.degree,
+                    hiding,
+                })
             }
-            Commands::Commit { coefficients } => {
-                let polynomial = Polynomial::try_from(coefficients)?;
+            Commands::ImportPtau { file, degree, out } => {
+                let out = artifact_path(out, SETUP_ARTIFACTS_FILE_NAME);
+                log::info!(
+                    "Starting to import the trusted setup from \"{}\"",
+                    file.display()
+                );
 
-                let polynomial_displayed = polynomial.to_string();
+                let reader = BufReader::new(fs::File::open(&file)?);
+                let mut ptau = trusted_setup::ptau::PtauFile::open(reader)?;
+                let header = ptau.header().clone();
+                log::debug!(
+                    "Found ptau file of power {} from a ceremony of power {}",
+                    header.power,
+                    header.ceremony_power
+                );
 
-                if polynomial.degree() > MAX_DEGREE {
-                    return Err(anyhow::anyhow!(
-                        "Only polynomials up to degree {MAX_DEGREE} are supported"
-                    )
-                    .into());
+                let count = header.tau_g2_count().min(degree as usize + 1);
+                if count < degree as usize + 1 {
+                    log::warn!(
+                        "The file only contains {count} powers, the setup is limited to degree {}",
+                        count - 1
+                    );
                 }
+                let setup = Setup::new(
+                    ptau.setup_artifacts(count)?,
+                    GenerationMode::Ptau {
+                        power: header.power,
+                        ceremony_power: header.ceremony_power,
+                    },
+                )?;
+
+                write_artifact(&out, &setup)?;
 
                 log::info!(
-                    "Starting to commit to the polynomial P(x) = \"{polynomial_displayed}\""
+                    "Trusted setup successfully imported with {count} artifacts. Artifacts have been written in \"{}\" with hash {}",
+                    out.display(),
+                    setup.hash()
                 );
 
-                if !fs::exists(SETUP_ARTIFACTS_PATH)? {
-                    return Err(anyhow::anyhow!(
-                        "Trusted setup artifacts have not been found, generate them beforehand."
-                    )
-                    .into());
-                }
+                Ok(CommandOutput::ImportPtau {
+                    setup_path: out,
+                    setup_hash: setup.hash().to_owned(),
+                    degree: setup.metadata.degree,
+                })
+            }
+            Commands::ExportSetup {
+                out,
+                setup,
+                g2_count,
+                lagrange,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                log::info!(
+                    "Starting to export the trusted setup to \"{}\"",
+                    out.display()
+                );
 
-                let file = fs::File::open(SETUP_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
+                let setup = read_setup(&setup_path)?;
 
-                let setup_artifacts: Vec<trusted_setup::SetupArtifact> =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
+                let text_setup = trusted_setup::text::TextSetup::from_setup_artifacts(
+                    &setup.artifacts,
+                    g2_count.unwrap_or(setup.artifacts.len()),
+                    lagrange,
+                )?;
 
-                let commitment = polynomial.commit(&setup_artifacts)?;
+                let file = fs::File::create(&out)?;
+                text_setup.write(BufWriter::new(file))?;
 
-                let commitment_artifact = serde_json::to_string(&CommitmentArtifact {
-                    polynomial,
-                    commitment,
+                log::info!(
+                    "Trusted setup successfully exported with {} G1 points and {} G2 points in \"{}\"",
+                    text_setup.g1_monomial.len(),
+                    text_setup.g2_monomial.len(),
+                    out.display()
+                );
+
+                Ok(CommandOutput::ExportSetup {
+                    out,
+                    g1_count: text_setup.g1_monomial.len(),
+                    g2_count: text_setup.g2_monomial.len(),
+                    lagrange,
                 })
-                .map_err(anyhow::Error::from)?;
+            }
+            Commands::ExportVerifierKey { setup, out } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+                let verifier_key = VerifierKeyArtifact::from_setup(&setup)?;
 
-                if fs::exists(COMMITMENT_ARTIFACTS_PATH)? {
-                    fs::remove_file(COMMITMENT_ARTIFACTS_PATH)?;
-                }
-                let mut file = fs::File::create(COMMITMENT_ARTIFACTS_PATH)?;
-                file.write_all(commitment_artifact.as_bytes())?;
+                let out = artifact_path(out, VERIFIER_KEY_FILE_NAME);
+                write_artifact(&out, &verifier_key)?;
+
+                log::info!("Verifier key successfully written in \"{}\"", out.display());
+
+                Ok(CommandOutput::ExportVerifierKey {
+                    verifier_key_path: out,
+                    setup_hash: verifier_key.setup_hash,
+                })
+            }
+            Commands::Commit {
+                polynomial,
+                setup,
+                out,
+                name,
+                degree_bound,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+
+                let polynomial = polynomial.read(strict, setup.max_degree())?;
+
+                let polynomial_displayed = polynomial.to_string();
 
                 log::info!(
-                    "Commitment to the polynomial \"P(x) = {polynomial_displayed}\" has been successfully generated."
+                    "Starting to commit to the polynomial P(x) = \"{polynomial_displayed}\""
+                );
+
+                check_supported_degree(&polynomial, &setup, &setup_path)?;
+
+                let commitment = polynomial.commit(&setup.artifacts)?;
+                let degree_bound = degree_bound
+                    .map(|bound| polynomial.prove_degree_bound(bound, &setup))
+                    .transpose()?;
+
+                let commitment_artifact = CommitmentArtifact {
+                    setup_hash: setup.hash().to_owned(),
+                    polynomial,
+                    commitment,
+                    degree_bound,
+                };
+                let out = match &name {
+                    Some(name) => Workspace::open(artifacts_dir)?
+                        .record_commitment(name, &commitment_artifact)?,
+                    None => {
+                        let out = artifact_path(out, COMMITMENT_ARTIFACTS_FILE_NAME);
+                        write_artifact(&out, &commitment_artifact)?;
+                        out
+                    }
+                };
+
+                log::info!(
+                    "Commitment to the polynomial \"P(x) = {polynomial_displayed}\" has been successfully generated in \"{}\".",
+                    out.display()
                 );
 
-                Ok(())
+                Ok(CommandOutput::Commit {
+                    commitment_path: out,
+                    name,
+                    polynomial: polynomial_displayed,
+                    degree: commitment_artifact.polynomial.degree(),
+                    commitment: to_hex(&commitment_artifact.commitment.to_compressed_bytes()),
+                    degree_bound: commitment_artifact
+                        .degree_bound
+                        .as_ref()
+                        .map(DegreeBoundOutput::from_proof),
+                    setup_hash: commitment_artifact.setup_hash,
+                })
             }
-            Commands::Evaluate { x } => {
+            Commands::Evaluate {
+                x,
+                aggregate,
+                setup,
+                commitment,
+                out,
+                name,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let points = x
+                    .iter()
+                    .map(|x| parse_scalar(x, strict))
+                    .collect::<Result<Vec<Scalar>, _>>()?;
+
                 log::info!(
-                    "Starting to evaluate the committed polynomial at input point \"x = {x}\""
+                    "Starting to evaluate the committed polynomial at input points {}",
+                    display_points(&points)
                 );
 
-                if !fs::exists(SETUP_ARTIFACTS_PATH)? {
-                    return Err(anyhow::anyhow!(
-                        "Trusted setup artifacts have not been found, generate them beforehand."
-                    )
-                    .into());
+                let setup = read_setup(&setup_path)?;
+
+                let mut workspace = match &name {
+                    Some(_) => Some(Workspace::open(artifacts_dir)?),
+                    None => None,
+                };
+                let out = artifact_path(out, EVALUATION_ARTIFACTS_FILE_NAME);
+                let (commitment_artifact, previous_evaluation_artifact) = match (&workspace, &name)
+                {
+                    (Some(workspace), Some(name)) => (
+                        workspace.read_commitment(name)?,
+                        match workspace.entry(name)?.evaluation_path {
+                            Some(_) => Some(workspace.read_evaluation(name)?),
+                            None => None,
+                        },
+                    ),
+                    _ => (
+                        read_artifact::<CommitmentArtifact>(
+                            &artifact_path(commitment, COMMITMENT_ARTIFACTS_FILE_NAME),
+                            "Commitment artifact has not been found, generate it beforehand.",
+                        )?,
+                        match fs::exists(&out)? {
+                            true => read_artifact::<EvaluationArtifact>(&out, "")
+                                .inspect_err(|e| log::warn!("Previous openings are discarded: {e}"))
+                                .ok(),
+                            false => None,
+                        },
+                    ),
+                };
+                check_setup_hash(&setup, "commitment", &commitment_artifact.setup_hash)?;
+
+                // Openings are accumulated as long as they belong to the same commitment and setup
+                let mut evaluation_artifact = match previous_evaluation_artifact {
+                    Some(previous)
+                        if previous.setup_hash == setup.hash()
+                            && previous.commitment == commitment_artifact.commitment =>
+                    {
+                        previous
+                    }
+                    _ => EvaluationArtifact::new(
+                        setup.hash().to_owned(),
+                        commitment_artifact.commitment.clone(),
+                    ),
+                };
+
+                let polynomial = &commitment_artifact.polynomial;
+                let mut openings = vec![];
+                if aggregate {
+                    let evaluation = MultiPointEvaluation::new(polynomial, &points)?;
+                    let proof = evaluation.generate_proof(polynomial, &setup.artifacts)?;
+                    let opening = AggregatedOpening { evaluation, proof };
+                    openings.push(OpeningOutput::from_aggregated_opening(&opening, None));
+                    evaluation_artifact.add_aggregated_opening(opening);
+                } else {
+                    for point in points {
+                        let evaluation = polynomial.evaluate(point)?;
+                        let proof = evaluation.generate_proof(polynomial, &setup.artifacts)?;
+                        let opening = Opening { evaluation, proof };
+                        openings.push(OpeningOutput::from_opening(&opening, None));
+                        evaluation_artifact.add_opening(opening);
+                    }
                 }
 
-                let file = fs::File::open(SETUP_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
+                let out = match (&mut workspace, &name) {
+                    (Some(workspace), Some(name)) => {
+                        workspace.record_evaluation(name, &evaluation_artifact)?
+                    }
+                    _ => {
+                        write_artifact(&out, &evaluation_artifact)?;
+                        out
+                    }
+                };
 
-                let setup_artifacts: Vec<trusted_setup::SetupArtifact> =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
+                for opening in &openings {
+                    log::info!(
+                        "Evaluation successful for polynomial: \"P(x) = {polynomial}\" with {}",
+                        opening.display_evaluations()
+                    );
+                }
+                log::info!(
+                    "{} opening(s) and {} aggregated opening(s) of the polynomial are stored in \"{}\"",
+                    evaluation_artifact.openings.len(),
+                    evaluation_artifact.aggregated_openings.len(),
+                    out.display()
+                );
+
+                Ok(CommandOutput::Evaluate {
+                    evaluation_path: out,
+                    name,
+                    polynomial: polynomial.to_string(),
+                    openings,
+                })
+            }
+            Commands::VerifyEvaluation {
+                setup,
+                commitment,
+                evaluation,
+                name,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
 
-                if !fs::exists(COMMITMENT_ARTIFACTS_PATH)? {
+                log::info!("Starting to verify the openings of the committed polynomial");
+
+                let setup = read_setup(&setup_path)?;
+
+                let (commitment_artifact, evaluation_artifact) = match &name {
+                    Some(name) => {
+                        let workspace = Workspace::open(artifacts_dir)?;
+                        (
+                            workspace.read_commitment(name)?,
+                            workspace.read_evaluation(name)?,
+                        )
+                    }
+                    None => (
+                        read_artifact::<CommitmentArtifact>(
+                            &artifact_path(commitment, COMMITMENT_ARTIFACTS_FILE_NAME),
+                            "Commitment artifact has not been found, generate it beforehand.",
+                        )?,
+                        read_artifact::<EvaluationArtifact>(
+                            &artifact_path(evaluation, EVALUATION_ARTIFACTS_FILE_NAME),
+                            "Evaluation artifact has not been found, generate it beforehand.",
+                        )?,
+                    ),
+                };
+                check_setup_hash(&setup, "commitment", &commitment_artifact.setup_hash)?;
+                check_setup_hash(&setup, "evaluation", &evaluation_artifact.setup_hash)?;
+                if evaluation_artifact.commitment != commitment_artifact.commitment {
                     return Err(anyhow::anyhow!(
-                        "Commitment artifact has not been found, generate it beforehand."
+                        "The evaluation artifact has been generated for another commitment, evaluate the polynomial again."
                     )
                     .into());
                 }
-                let file = fs::File::open(COMMITMENT_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
-                let commitment_artifact: CommitmentArtifact =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
-
-                let evaluation = commitment_artifact
-                    .polynomial
-                    .evaluate(Scalar::from_i128(x))?;
-                let proof =
-                    evaluation.generate_proof(&commitment_artifact.polynomial, &setup_artifacts)?;
-
-                let evaluation_artifact = serde_json::to_string(&EvaluationArtifact {
-                    evaluation: evaluation.clone(),
-                    proof,
+
+                let commitment = &commitment_artifact.commitment;
+                let mut openings = vec![];
+                for opening in &evaluation_artifact.openings {
+                    let verified = opening.evaluation.verify_proof(
+                        &opening.proof,
+                        commitment,
+                        &setup.artifacts,
+                    )?;
+                    openings.push(OpeningOutput::from_opening(opening, Some(verified)));
+                }
+                for opening in &evaluation_artifact.aggregated_openings {
+                    let verified = opening.evaluation.verify_proof(
+                        &opening.proof,
+                        commitment,
+                        &setup.artifacts,
+                    )?;
+                    openings.push(OpeningOutput::from_aggregated_opening(
+                        opening,
+                        Some(verified),
+                    ));
+                }
+
+                let mut verified = true;
+                for opening in &openings {
+                    if opening.verified == Some(true) {
+                        log::info!(
+                            "Successfully verified evaluation for polynomial \"P(x) = {}\" with {}",
+                            commitment_artifact.polynomial,
+                            opening.display_evaluations()
+                        );
+                    } else {
+                        verified = false;
+                        log::error!(
+                            "The proof associated to the evaluation with {} is incorrect.",
+                            opening.display_evaluations()
+                        );
+                    }
+                }
+                if openings.is_empty() {
+                    log::warn!("No opening has been found in the evaluation artifact");
+                }
+
+                let degree_bound = match &commitment_artifact.degree_bound {
+                    Some(proof) => {
+                        let bound_verified = proof.verify(commitment, &setup)?;
+                        if bound_verified {
+                            log::info!(
+                                "Successfully verified that the committed polynomial has a degree lower or equal to {}",
+                                proof.bound
+                            );
+                        } else {
+                            verified = false;
+                            log::error!(
+                                "The proof that the committed polynomial has a degree lower or equal to {} is incorrect.",
+                                proof.bound
+                            );
+                        }
+                        Some(DegreeBoundOutput {
+                            verified: Some(bound_verified),
+                            ..DegreeBoundOutput::from_proof(proof)
+                        })
+                    }
+                    None => None,
+                };
+
+                Ok(CommandOutput::VerifyEvaluation {
+                    verified,
+                    polynomial: commitment_artifact.polynomial.to_string(),
+                    commitment: to_hex(&commitment.to_compressed_bytes()),
+                    degree_bound,
+                    openings,
                 })
-                .map_err(anyhow::Error::from)?;
+            }
+            Commands::Inspect { file } => {
+                let content = fs::read(&file).map_err(|e| {
+                    anyhow::anyhow!("Unable to read the artifact \"{}\": {e}", file.display())
+                })?;
+                let report = inspect::inspect(&content)?;
 
-                if fs::exists(EVALUATION_ARTIFACTS_PATH)? {
-                    fs::remove_file(EVALUATION_ARTIFACTS_PATH)?;
+                log::info!("Inspection of \"{}\"", file.display());
+                for line in report.to_string().lines() {
+                    log::info!("{line}");
                 }
-                let mut file = fs::File::create(EVALUATION_ARTIFACTS_PATH)?;
-                file.write_all(evaluation_artifact.as_bytes())?;
+
+                Ok(CommandOutput::Inspect {
+                    valid: report.is_valid(),
+                    report,
+                })
+            }
+            Commands::Prove {
+                polynomial,
+                x,
+                fiat_shamir,
+                hiding,
+                setup,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+                let polynomial = polynomial.read(strict, setup.max_degree())?;
+                let x = x.map(|x| parse_scalar(&x, strict)).transpose()?;
 
                 log::info!(
-                    "Evaluation successful for polynomial: \"P(x) = {}\" at point \"x = {x}\" with \"P({x}) = {}\"",
-                    commitment_artifact.polynomial,
+                    "Starting to prove the evaluation of the polynomial \"P(x) = {polynomial}\""
+                );
+
+                check_supported_degree(&polynomial, &setup, &setup_path)?;
+
+                let blinding = hiding.then(|| Polynomial::random(polynomial.degree()));
+                let commitment = match &blinding {
+                    Some(blinding) => polynomial.commit_hiding(blinding, &setup)?,
+                    None => polynomial.commit(&setup.artifacts)?,
+                };
+                let x = match x {
+                    Some(x) => x,
+                    None => {
+                        let x = fiat_shamir_point(&setup, polynomial.degree(), &commitment)?;
+                        log::info!("Input point \"x = {x}\" derived from the transcript");
+                        x
+                    }
+                };
+                let (evaluation, blinding_result, proof) = match &blinding {
+                    Some(blinding) => {
+                        let evaluation = polynomial.evaluate_hiding(blinding, x)?;
+                        let proof = evaluation.generate_proof(&polynomial, blinding, &setup)?;
+                        let HidingEvaluation {
+                            point,
+                            result,
+                            blinding_result,
+                        } = evaluation;
+                        (Evaluation { point, result }, Some(blinding_result), proof)
+                    }
+                    None => {
+                        let evaluation = polynomial.evaluate(x)?;
+                        let proof = evaluation.generate_proof(&polynomial, &setup.artifacts)?;
+                        (evaluation, None, proof)
+                    }
+                };
+
+                let commitment = to_hex(&commitment.to_compressed_bytes());
+                let proof = to_hex(&proof.to_compressed_bytes());
+                let point_argument = if fiat_shamir {
+                    format!("--fiat-shamir --degree {}", polynomial.degree())
+                } else {
+                    format!("--x {}", evaluation.point)
+                };
+                let blinding_argument = match &blinding_result {
+                    Some(blinding_result) => format!(" --blinding-y {blinding_result}"),
+                    None => String::new(),
+                };
+                log::info!(
+                    "Evaluation successfully proven, verify it with: verify --commitment {commitment} {point_argument} --y {}{blinding_argument} --proof {proof}",
                     evaluation.result
                 );
 
-                Ok(())
+                Ok(CommandOutput::Prove {
+                    polynomial: polynomial.to_string(),
+                    degree: polynomial.degree(),
+                    fiat_shamir,
+                    commitment,
+                    x: evaluation.point.to_string(),
+                    y: evaluation.result.to_string(),
+                    blinding_y: blinding_result.map(|b| b.to_string()),
+                    proof,
+                    setup_hash: setup.hash().to_owned(),
+                })
             }
-            Commands::VerifyEvaluation {} => {
-                log::info!("Starting to verify the previous polynomial evaluation");
+            Commands::Verify {
+                commitment,
+                x,
+                fiat_shamir: _,
+                degree,
+                blinding_y,
+                y,
+                proof,
+                setup,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let commitment_point = parse_g1_point(&commitment, "commitment")?;
+                let proof_point = parse_g1_point(&proof, "proof")?;
+                let x = x.map(|x| parse_scalar(&x, strict)).transpose()?;
+                let y = parse_scalar(&y, strict)?;
+                let blinding_y = blinding_y.map(|b| parse_scalar(&b, strict)).transpose()?;
 
-                if !fs::exists(SETUP_ARTIFACTS_PATH)? {
-                    return Err(anyhow::anyhow!(
-                        "Trusted setup artifacts have not been found, generate them beforehand."
-                    )
-                    .into());
-                }
+                let setup = read_setup(&setup_path)?;
 
-                let file = fs::File::open(SETUP_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
+                let point = match (x, degree) {
+                    (Some(x), _) => x,
+                    (None, Some(degree)) => fiat_shamir_point(&setup, degree, &commitment_point)?,
+                    (None, None) => unreachable!("the parser requires `--x` or `--degree`"),
+                };
+                let evaluation = Evaluation { point, result: y };
 
-                let setup_artifacts: Vec<trusted_setup::SetupArtifact> =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
+                log::info!(
+                    "Starting to verify the evaluation \"P({}) = {}\" of the committed polynomial",
+                    evaluation.point,
+                    evaluation.result
+                );
 
-                if !fs::exists(COMMITMENT_ARTIFACTS_PATH)? {
-                    return Err(anyhow::anyhow!(
-                        "Commitment artifact has not been found, generate it beforehand."
-                    )
-                    .into());
+                let verified = match blinding_y {
+                    Some(blinding_result) => HidingEvaluation {
+                        point: evaluation.point.clone(),
+                        result: evaluation.result.clone(),
+                        blinding_result,
+                    }
+                    .verify_proof(&proof_point, &commitment_point, &setup)?,
+                    None => evaluation.verify_proof(
+                        &proof_point,
+                        &commitment_point,
+                        &setup.artifacts,
+                    )?,
+                };
+                if verified {
+                    log::info!(
+                        "Successfully verified the evaluation \"P({}) = {}\"",
+                        evaluation.point,
+                        evaluation.result
+                    );
+                } else {
+                    log::error!("The proof associated to the evaluation is incorrect.");
                 }
-                let file = fs::File::open(COMMITMENT_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
-                let commitment_artifact: CommitmentArtifact =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
 
-                if !fs::exists(EVALUATION_ARTIFACTS_PATH)? {
-                    return Err(anyhow::anyhow!(
-                        "Evaluation artifact has not been found, generate it beforehand."
-                    )
-                    .into());
-                }
-                let file = fs::File::open(EVALUATION_ARTIFACTS_PATH)?;
-                let reader = BufReader::new(file);
-                let evaluation_artifact: EvaluationArtifact =
-                    serde_json::from_reader(reader).map_err(anyhow::Error::from)?;
-
-                let is_proof_ok = evaluation_artifact.evaluation.verify_proof(
-                    &evaluation_artifact.proof,
-                    &commitment_artifact.commitment,
-                    &setup_artifacts,
+                Ok(CommandOutput::Verify {
+                    verified,
+                    commitment: to_hex(&commitment_point.to_compressed_bytes()),
+                    x: evaluation.point.to_string(),
+                    y: evaluation.result.to_string(),
+                    proof: to_hex(&proof_point.to_compressed_bytes()),
+                })
+            }
+            Commands::Repl { setup } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+
+                log::info!(
+                    "Starting the session with the setup \"{}\" of degree {}, type `help` to list the commands",
+                    setup_path.display(),
+                    setup.metadata.degree
+                );
+
+                let setup_hash = setup.hash().to_owned();
+                Repl::new(setup.artifacts).run(std::io::stdin().lock(), std::io::stdout())?;
+
+                Ok(CommandOutput::Repl { setup_hash })
+            }
+            Commands::Serve {
+                address,
+                setup,
+                max_body_size,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+                let setup_hash = setup.hash().to_owned();
+
+                let server = Server::bind(
+                    &address,
+                    setup,
+                    ServerOptions {
+                        max_body_size,
+                        strict,
+                    },
                 )?;
+                log::info!(
+                    "Serving the setup \"{}\" with hash {setup_hash} on http://{}",
+                    setup_path.display(),
+                    server
+                        .local_addr()
+                        .map_or(address.clone(), |a| a.to_string())
+                );
+                server.run()?;
 
-                if !is_proof_ok {
-                    return Err(anyhow::anyhow!(
-                        "The proof associated to the evaluation is incorrect."
-                    )
-                    .into());
+                Ok(CommandOutput::Serve {
+                    address,
+                    setup_hash,
+                })
+            }
+            Commands::Prover {
+                connect,
+                polynomial,
+                setup,
+                transcript,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+                let polynomial = polynomial.read(strict, setup.max_degree())?;
+                check_supported_degree(&polynomial, &setup, &setup_path)?;
+
+                log::info!(
+                    "Connecting to the verifier at \"{connect}\" to prove the polynomial \"P(x) = {polynomial}\""
+                );
+                let mut channel = Channel::new(connect.connect()?);
+                let result = protocol::run_prover(&mut channel, &polynomial, &setup);
+                let transcript = record_transcript(&channel.transcript, transcript.as_deref())?;
+
+                let verified = result?;
+                if verified {
+                    log::info!("The verifier accepted the opening");
+                } else {
+                    log::error!("The verifier rejected the opening");
+                }
+                Ok(CommandOutput::Prover {
+                    verified,
+                    transcript,
+                })
+            }
+            Commands::Verifier {
+                listen,
+                setup,
+                transcript,
+            } => {
+                let setup_path = artifact_path(setup, SETUP_ARTIFACTS_FILE_NAME);
+                let setup = read_setup(&setup_path)?;
+
+                log::info!("Waiting for a prover on \"{listen}\"");
+                let mut channel = Channel::new(listen.accept()?);
+
+                let mut challenge_be_bytes = [0; 64];
+                rand::rng().fill_bytes(&mut challenge_be_bytes);
+                let challenge = Scalar::from_be_bytes_wide(&challenge_be_bytes);
+
+                let result = protocol::run_verifier(&mut channel, &setup, challenge);
+                let transcript = record_transcript(&channel.transcript, transcript.as_deref())?;
+
+                let verified = result?;
+                if verified {
+                    log::info!("Successfully verified the opening of the prover");
+                } else {
+                    log::error!("The opening of the prover is incorrect");
                 }
+                Ok(CommandOutput::Verifier {
+                    verified,
+                    transcript,
+                })
+            }
+            Commands::List {} => {
+                let workspace = Workspace::open(artifacts_dir)?;
 
+                let mut count = 0;
+                for (name, entry) in workspace.entries() {
+                    count += 1;
+                    log::info!(
+                        "\"{name}\": degree {}, commitment 0x{}, setup {}, {}",
+                        entry.degree,
+                        entry.commitment,
+                        entry.setup_hash,
+                        match entry.evaluation_path {
+                            Some(_) => "evaluated",
+                            None => "not evaluated",
+                        }
+                    );
+                }
                 log::info!(
-                    "Successfully verified evaluation for polynomial \"P(x) = {}\" at point \"x = {}\" with \"P({}) = {}\"",
-                    commitment_artifact.polynomial,
-                    evaluation_artifact.evaluation.point,
-                    evaluation_artifact.evaluation.point,
-                    evaluation_artifact.evaluation.result
+                    "{count} named commitment(s) in the workspace \"{}\"",
+                    artifacts_dir.display()
                 );
 
-                Ok(())
+                Ok(CommandOutput::List {
+                    commitments: workspace
+                        .entries()
+                        .map(|(name, entry)| ListedCommitment {
+                            name: name.clone(),
+                            degree: entry.degree,
+                            commitment: format!("0x{}", entry.commitment),
+                            setup_hash: entry.setup_hash.clone(),
+                            evaluated: entry.evaluation_path.is_some(),
+                        })
+                        .collect(),
+                })
             }
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct CommitmentArtifact {
-    polynomial: Polynomial,
-    commitment: G1Point,
+/// Derives the input point of a non-interactive opening from a Fiat–Shamir transcript of the setup hash, the degree of the polynomial and the commitment
+///
+/// * `setup` - Setup of the commitment
+/// * `degree` - Degree of the committed polynomial
+/// * `commitment` - Commitment to the polynomial
+fn fiat_shamir_point(setup: &Setup, degree: u32, commitment: &G1Point) -> Result<Scalar, CliError> {
+    let setup_hash = hex::decode(setup.hash())
+        .map_err(|e| anyhow::anyhow!("Invalid setup hash \"{}\": {e}", setup.hash()))?;
+    let mut transcript = Transcript::new(FIAT_SHAMIR_PROTOCOL);
+    transcript.absorb_bytes(b"setup_hash", &setup_hash);
+    transcript.absorb_bytes(b"degree", &degree.to_be_bytes());
+    transcript.absorb_g1_point(b"commitment", commitment);
+    Ok(transcript.squeeze_scalar(b"x"))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct EvaluationArtifact {
-    evaluation: Evaluation,
-    proof: G1Point,
+/// Logs the transcript of an interactive session, writes it in a file if requested and returns its lines
+///
+/// * `transcript` - Messages exchanged during the session
+/// * `path` - Path of the transcript file
+fn record_transcript(
+    transcript: &protocol::Transcript,
+    path: Option<&Path>,
+) -> Result<Vec<String>, CliError> {
+    let displayed = transcript.to_string();
+    for line in displayed.lines() {
+        log::info!("{line}");
+    }
+    if let Some(path) = path {
+        fs::write(path, &displayed)?;
+        log::info!("Transcript written in \"{}\"", path.display());
+    }
+    Ok(displayed.lines().map(String::from).collect())
+}
+
+/// Parses a field element given on the command line
+///
+/// * `value` - Decimal or hexadecimal value
+/// * `strict` - Whether the non-canonical values must be rejected instead of being reduced modulo r
+fn parse_scalar(value: &str, strict: bool) -> Result<Scalar, CliError> {
+    let scalar = if strict {
+        Scalar::from_str_strict(value)?
+    } else {
+        Scalar::from_str(value)?
+    };
+    Ok(scalar)
+}
+
+/// Parses a compressed G1 point given in hexadecimal on the command line, the point must be in the G1 subgroup
+///
+/// * `value` - Compressed point in hexadecimal, with or without `0x` prefix
+/// * `name` - Name of the point, used in error messages
+fn parse_g1_point(value: &str, name: &str) -> Result<G1Point, CliError> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Invalid hexadecimal for the {name}: {e}"))?;
+    Ok(G1Point::from_compressed_bytes_in_group(&bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid {name} point: {e}"))?)
+}
+
+/// Checks that the setup allows to commit to the polynomial
+///
+/// * `polynomial` - Polynomial to commit to
+/// * `setup` - Setup of the commitment
+/// * `setup_path` - Path of the setup, used in the error message
+fn check_supported_degree(
+    polynomial: &Polynomial,
+    setup: &Setup,
+    setup_path: &Path,
+) -> Result<(), CliError> {
+    if polynomial.degree() > setup.metadata.degree {
+        return Err(anyhow::anyhow!(
+            "The polynomial has degree {} while the setup \"{}\" only supports polynomials up to degree {}, generate a setup with a larger degree.",
+            polynomial.degree(),
+            setup_path.display(),
+            setup.metadata.degree
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Reads the setup and checks that its metadata are consistent with its artifacts
+///
+/// * `path` - Path of the setup artifacts
+fn read_setup(path: &Path) -> Result<Setup, CliError> {
+    let setup: Setup = read_artifact(
+        path,
+        "Trusted setup artifacts have not been found, generate them beforehand.",
+    )?;
+    setup.validate()?;
+    if setup.metadata.insecure {
+        log::debug!("The trusted setup has been generated from a single secret, it is insecure");
+    }
+    Ok(setup)
 }
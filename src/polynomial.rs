@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+pub mod batch;
+pub mod degree_bound;
+pub mod expression;
+pub mod file;
+pub mod fk20;
+pub mod gwc;
+pub mod hiding;
+pub mod shplonk;
+
 use super::{
     curves::{G1Point, G2Point, bilinear_map},
+    domain::Domain,
     scalar::Scalar,
+    transcript::Transcript,
     trusted_setup::SetupArtifact,
 };
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Polynomial {
     coefficients: Vec<Scalar>,
 }
@@ -89,6 +100,16 @@ impl From<Scalar> for Polynomial {
 }
 
 impl Polynomial {
+    /// Creates the polynomial taking the given values over the domain of the roots of unity of the same size
+    ///
+    /// * `evaluations` - Evaluations at `1, ω, ..., ω^(n - 1)`, their number `n` must be a power of two
+    pub fn from_evaluations(evaluations: Vec<Scalar>) -> Result<Self, anyhow::Error> {
+        let domain = Domain::new(evaluations.len()).map_err(|e| {
+            anyhow::anyhow!("Evaluations must be given over a domain of roots of unity: {e}")
+        })?;
+        Self::try_from(domain.ifft(&evaluations)?)
+    }
+
     /// Return the degree of the polynomial.
     ///
     /// Degree is derived as one minus the number of coefficients.
@@ -194,13 +215,166 @@ impl Polynomial {
         Polynomial::try_from(quotient_coefficients_in_descending_degree)
     }
 
+    /// Multiplies the polynomial by another one
+    ///
+    /// * `other` - Polynomial to multiply the current one with
+    pub fn mul(&self, other: &Self) -> Result<Self, anyhow::Error> {
+        if self.coefficients.is_empty() || other.coefficients.is_empty() {
+            return Polynomial::try_from(Vec::<Scalar>::new());
+        }
+        let mut coefficients =
+            vec![Scalar::from_i128(0); self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, a) in self.coefficients.iter().enumerate() {
+            for (j, b) in other.coefficients.iter().enumerate() {
+                coefficients[i + j] = coefficients[i + j].add(&a.mul(b));
+            }
+        }
+        Polynomial::try_from(coefficients)
+    }
+
+    /// Divides the polynomial by a divisor polynomial and returns the quotient and the remainder
+    ///
+    /// * `divisor` - Non zero polynomial dividing the current one
+    pub fn divide(&self, divisor: &Self) -> Result<(Self, Self), anyhow::Error> {
+        let divisor_leading_coefficient = match divisor.coefficients.last() {
+            Some(c) if !c.is_zero() => c,
+            _ => return Err(anyhow::anyhow!("Unable to divide by the zero polynomial")),
+        };
+        if self.coefficients.len() < divisor.coefficients.len() {
+            return Ok((
+                Polynomial::try_from(Vec::<Scalar>::new())?,
+                Polynomial::try_from(self.coefficients.clone())?,
+            ));
+        }
+
+        let leading_coefficient_inv = divisor_leading_coefficient.inv()?;
+        let divisor_length = divisor.coefficients.len();
+        let mut remainder = self.coefficients.clone();
+        let mut quotient = vec![Scalar::from_i128(0); remainder.len() - divisor_length + 1];
+        // Each step cancels the highest remaining coefficient of the remainder
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_length - 1].mul(&leading_coefficient_inv);
+            for (j, d) in divisor.coefficients.iter().enumerate() {
+                remainder[i + j] = remainder[i + j].sub(&factor.mul(d));
+            }
+            quotient[i] = factor;
+        }
+        remainder.truncate(divisor_length - 1);
+
+        Ok((
+            Polynomial::try_from(quotient)?,
+            Polynomial::try_from(remainder)?,
+        ))
+    }
+
+    /// Creates the vanishing polynomial `(x - z_1) * ... * (x - z_k)` of a set of points
+    ///
+    /// * `points` - Roots of the polynomial
+    pub fn vanishing(points: &[Scalar]) -> Result<Self, anyhow::Error> {
+        let mut vanishing = Polynomial::from_constant(Scalar::from_i128(1));
+        for point in points {
+            vanishing = vanishing.mul(&Polynomial::try_from(vec![
+                point.neg(),
+                Scalar::from_i128(1),
+            ])?)?;
+        }
+        Ok(vanishing)
+    }
+
+    /// Creates the polynomial of lowest degree going through all the evaluations, using Lagrange interpolation.
+    ///
+    /// * `evaluations` - Evaluations of the polynomial, their points must be distinct
+    pub fn interpolate(evaluations: &[Evaluation]) -> Result<Self, anyhow::Error> {
+        let mut interpolated = Polynomial::try_from(Vec::<Scalar>::new())?;
+        for (i, evaluation) in evaluations.iter().enumerate() {
+            // L_i(x) = prod_{j != i} (x - z_j) / (z_i - z_j)
+            let mut numerator = Polynomial::from_constant(Scalar::from_i128(1));
+            let mut denominator = Scalar::from_i128(1);
+            for (j, other) in evaluations.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                numerator = numerator.mul(&Polynomial::try_from(vec![
+                    other.point.neg(),
+                    Scalar::from_i128(1),
+                ])?)?;
+                denominator = denominator.mul(&evaluation.point.sub(&other.point));
+            }
+            let factor = evaluation.result.mul(&denominator.inv().map_err(|_| {
+                anyhow::anyhow!(
+                    "Unable to interpolate, the point {} is given more than once",
+                    evaluation.point
+                )
+            })?);
+            interpolated = interpolated.add(&numerator.scale(&factor))?;
+        }
+        Ok(interpolated)
+    }
+
+    /// Adds a polynomial to the current one
+    ///
+    /// * `other` - Polynomial to add to the current one
+    pub fn add(&self, other: &Self) -> Result<Self, anyhow::Error> {
+        let length = self.coefficients.len().max(other.coefficients.len());
+        let zero = Scalar::from_i128(0);
+        let coefficients = (0..length)
+            .map(|i| {
+                self.coefficients
+                    .get(i)
+                    .unwrap_or(&zero)
+                    .add(other.coefficients.get(i).unwrap_or(&zero))
+            })
+            .collect::<Vec<Scalar>>();
+        Polynomial::try_from(coefficients)
+    }
+
+    /// Multiplies every coefficient of the polynomial by a scalar
+    ///
+    /// The trailing zero coefficients are removed as in `try_from`, scaling by zero gives the zero polynomial.
+    ///
+    /// * `factor` - Scalar multiplying the polynomial
+    pub fn scale(&self, factor: &Scalar) -> Self {
+        let mut coefficients: Vec<Scalar> =
+            self.coefficients.iter().map(|c| c.mul(factor)).collect();
+        let length = coefficients
+            .iter()
+            .rposition(|c| !c.is_zero())
+            .map_or(1, |i| i + 1);
+        coefficients.truncate(length);
+        Polynomial { coefficients }
+    }
+
+    /// Generate the G2Point representing the commit to the polynomial using setup artifacts.
+    ///
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `degree + 1` artifacts.
+    pub fn commit_g2(&self, setup_artifacts: &[SetupArtifact]) -> Result<G2Point, anyhow::Error> {
+        if (self.degree() + 1) as usize > setup_artifacts.len() {
+            return Err(anyhow::anyhow!(
+                "Setup does not allow for commitment generation of the polynomial in G2. The polynomial of degree {} requires {} setup artifacts, got {}.",
+                self.degree(),
+                self.degree() + 1,
+                setup_artifacts.len()
+            ));
+        }
+
+        let mut commitment = G2Point::from_i128(0);
+        for (i, coefficient) in self.coefficients.iter().enumerate() {
+            commitment = commitment.add(&setup_artifacts[i].g2.mult(coefficient));
+        }
+
+        Ok(commitment)
+    }
+
     /// Generate the G1Point representing the commit to the polynomial using setup artifacts.
     ///
     /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `degree + 1` artifacts.
     pub fn commit(&self, setup_artifacts: &[SetupArtifact]) -> Result<G1Point, anyhow::Error> {
         if (self.degree() + 1) as usize > setup_artifacts.len() {
             return Err(anyhow::anyhow!(
-                "Setup does not allow for commitment generation of the polynomial. The polynomial degree is too high."
+                "Setup does not allow for commitment generation of the polynomial. The polynomial of degree {} requires {} setup artifacts, got {}.",
+                self.degree(),
+                self.degree() + 1,
+                setup_artifacts.len()
             ));
         }
 
@@ -253,6 +427,15 @@ pub struct Evaluation {
 }
 
 impl Evaluation {
+    /// Returns the quotient `(P(x) - y) / (x - z)` committed by the Kate proof of the evaluation
+    ///
+    /// * `polynomial` - The polynomial associated with the evaluation
+    pub fn quotient(&self, polynomial: &Polynomial) -> Result<Polynomial, anyhow::Error> {
+        polynomial
+            .sub(&Polynomial::from_constant(self.result.clone()))?
+            .divide_by_root(&self.point)
+    }
+
     /// Generates a Kate proof for a given evaluation
     ///
     /// * `polynomial` - The polynomial associated with the evaluation
@@ -262,10 +445,7 @@ impl Evaluation {
         polynomial: &Polynomial,
         setup_artifacts: &[SetupArtifact],
     ) -> Result<G1Point, anyhow::Error> {
-        polynomial
-            .sub(&Polynomial::from_constant(self.result.clone()))?
-            .divide_by_root(&self.point)?
-            .commit(setup_artifacts)
+        self.quotient(polynomial)?.commit(setup_artifacts)
     }
 
     /// Verify the Kate proof given a proof, a commitment and the setup artifacts
@@ -279,6 +459,11 @@ impl Evaluation {
         commitment: &G1Point,
         setup_artifacts: &[SetupArtifact],
     ) -> Result<bool, anyhow::Error> {
+        if setup_artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "The verification requires at least 2 setup artifacts"
+            ));
+        }
         let lhs = bilinear_map(
             proof,
             &setup_artifacts[1]
@@ -294,6 +479,143 @@ impl Evaluation {
     }
 }
 
+/// Evaluations of a polynomial at several distinct points, proven together by a single proof.
+///
+/// With `I` the polynomial interpolating the evaluations and `Z` the vanishing polynomial of the points,
+/// the proof is the commitment to `q = (P - I) / Z` and the verifier checks `e(C - [I(s)]_1, G2) = e(proof, [Z(s)]_2)`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiPointEvaluation {
+    pub evaluations: Vec<Evaluation>,
+}
+
+impl MultiPointEvaluation {
+    /// Evaluates the polynomial at each point
+    ///
+    /// * `polynomial` - Evaluated polynomial
+    /// * `points` - Distinct input points
+    pub fn new(polynomial: &Polynomial, points: &[Scalar]) -> Result<Self, anyhow::Error> {
+        let evaluations = points
+            .iter()
+            .map(|point| polynomial.evaluate(point.clone()))
+            .collect::<Result<Vec<Evaluation>, _>>()?;
+        Ok(Self { evaluations })
+    }
+
+    /// Returns the evaluated points
+    pub fn points(&self) -> Vec<Scalar> {
+        self.evaluations.iter().map(|e| e.point.clone()).collect()
+    }
+
+    /// Generates the proof of all the evaluations
+    ///
+    /// * `polynomial` - The polynomial associated with the evaluations
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `degree` artifacts.
+    pub fn generate_proof(
+        &self,
+        polynomial: &Polynomial,
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<G1Point, anyhow::Error> {
+        let interpolated = Polynomial::interpolate(&self.evaluations)?;
+        let (quotient, remainder) = polynomial
+            .sub(&interpolated)?
+            .divide(&Polynomial::vanishing(&self.points())?)?;
+        if !remainder.coefficients.iter().all(Scalar::is_zero) {
+            return Err(anyhow::anyhow!(
+                "The evaluations do not match the polynomial, unable to generate the proof"
+            ));
+        }
+        quotient.commit(setup_artifacts)
+    }
+
+    /// Verifies the proof of all the evaluations
+    ///
+    /// * `proof` - Proof of the evaluations
+    /// * `commitment` - Commitment of the underlying polynomial
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `k + 1` artifacts for `k` evaluations.
+    pub fn verify_proof(
+        &self,
+        proof: &G1Point,
+        commitment: &G1Point,
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<bool, anyhow::Error> {
+        let interpolated = Polynomial::interpolate(&self.evaluations)?;
+        let vanishing = Polynomial::vanishing(&self.points())?;
+
+        let lhs = bilinear_map(proof, &vanishing.commit_g2(setup_artifacts)?);
+        let rhs = bilinear_map(
+            &commitment.sub(&interpolated.commit(setup_artifacts)?),
+            &G2Point::from_i128(1),
+        );
+
+        Ok(lhs == rhs)
+    }
+}
+
+/// Checks the openings of several polynomials, each on its own set of points, batched by the GWC and Shplonk schemes
+///
+/// There must be one non-empty set of distinct points per polynomial.
+///
+/// * `count` - Number of polynomials or commitments
+/// * `evaluations` - Evaluations of each polynomial, in the same order
+pub(crate) fn check_batched_openings(
+    count: usize,
+    evaluations: &[MultiPointEvaluation],
+) -> Result<(), anyhow::Error> {
+    if count == 0 {
+        return Err(anyhow::anyhow!("At least one polynomial must be opened"));
+    }
+    if count != evaluations.len() {
+        return Err(anyhow::anyhow!(
+            "Got {count} polynomials but {} sets of evaluations",
+            evaluations.len()
+        ));
+    }
+    for (i, evaluation) in evaluations.iter().enumerate() {
+        let points = evaluation.points();
+        if points.is_empty() {
+            return Err(anyhow::anyhow!(
+                "The set of evaluations {i} does not contain any point"
+            ));
+        }
+        if let Some(point) = points
+            .iter()
+            .enumerate()
+            .find_map(|(j, point)| points[..j].contains(point).then_some(point))
+        {
+            return Err(anyhow::anyhow!(
+                "The set of evaluations {i} contains the point {point} more than once"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Creates the transcript of batched openings absorbing the commitments and the evaluations
+///
+/// * `protocol` - Label of the batching scheme
+/// * `commitments` - Commitments of the polynomials
+/// * `evaluations` - Evaluations of each polynomial, in the same order
+pub(crate) fn batched_openings_transcript(
+    protocol: &[u8],
+    commitments: &[G1Point],
+    evaluations: &[MultiPointEvaluation],
+) -> Transcript {
+    let mut transcript = Transcript::new(protocol);
+    transcript.absorb_bytes(b"size", &(commitments.len() as u64).to_be_bytes());
+    for (commitment, evaluation) in commitments.iter().zip(evaluations) {
+        transcript.absorb_g1_point(b"commitment", commitment);
+        transcript.absorb_bytes(
+            b"points",
+            &(evaluation.evaluations.len() as u64).to_be_bytes(),
+        );
+        for e in &evaluation.evaluations {
+            transcript.absorb_scalar(b"point", &e.point);
+            transcript.absorb_scalar(b"result", &e.result);
+        }
+    }
+    transcript
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,5 +640,56 @@ mod tests {
                 .degree(),
             5
         );
+
+        let p = Polynomial::try_from(vec![1, 0, 1, 0, 0, 5]).unwrap();
+        let scaled = p.scale(&Scalar::from_i128(0));
+        assert_eq!(scaled.degree(), 0);
+        assert_eq!(scaled.coefficients, vec![Scalar::from_i128(0)]);
+        assert_eq!(p.scale(&Scalar::from_i128(2)).degree(), 5);
+    }
+
+    #[test]
+    fn test_polynomial_from_evaluations() {
+        let domain = Domain::new(4).unwrap();
+        let evaluations: Vec<Scalar> = [5, -1, 7, 0].into_iter().map(Scalar::from_i128).collect();
+        let polynomial = Polynomial::from_evaluations(evaluations.clone()).unwrap();
+        for (x, y) in domain.elements().into_iter().zip(evaluations) {
+            assert_eq!(polynomial.evaluate(x).unwrap().result, y);
+        }
+        assert!(Polynomial::from_evaluations(vec![Scalar::from_i128(1); 3]).is_err());
+    }
+
+    #[test]
+    fn test_polynomial_division_and_interpolation() {
+        let p = Polynomial::try_from(vec![3, -1, 4, 1, 5]).unwrap();
+        let divisor = Polynomial::try_from(vec![2, 0, 7]).unwrap();
+        let (quotient, remainder) = p.divide(&divisor).unwrap();
+        assert!(remainder.degree() < divisor.degree());
+        let rebuilt = quotient.mul(&divisor).unwrap().add(&remainder).unwrap();
+        assert_eq!(rebuilt.coefficients, p.coefficients);
+
+        let evaluations = MultiPointEvaluation::new(
+            &p,
+            &[1, -2, 9]
+                .into_iter()
+                .map(Scalar::from_i128)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+        .evaluations;
+        let interpolated = Polynomial::interpolate(&evaluations).unwrap();
+        assert_eq!(interpolated.degree(), 2);
+        for evaluation in &evaluations {
+            assert_eq!(
+                interpolated
+                    .evaluate(evaluation.point.clone())
+                    .unwrap()
+                    .result,
+                evaluation.result
+            );
+        }
+
+        let duplicated = [evaluations[0].clone(), evaluations[0].clone()];
+        assert!(Polynomial::interpolate(&duplicated).is_err());
     }
 }
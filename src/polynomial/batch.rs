@@ -0,0 +1,194 @@
+//! Openings of several polynomials at the same point with a single proof.
+//!
+//! The polynomials `p_i` are combined with the powers of a challenge `γ` into `p = Σ γ^i p_i`, whose commitment
+//! `C = Σ γ^i C_i` and evaluation `y = Σ γ^i y_i` are derived by the verifier from the commitments and evaluations
+//! of each polynomial. The proof is the Kate proof of `p` at `z`, it is checked with a single pairing equation.
+//! The challenge is derived with the Fiat–Shamir heuristic from the point, the commitments and the evaluations.
+use super::{Evaluation, Polynomial};
+use crate::{
+    curves::G1Point, scalar::Scalar, transcript::Transcript, trusted_setup::SetupArtifact,
+};
+
+const SAME_POINT_PROTOCOL: &[u8] = b"kzg-poly-commit-exploration/batch-same-point/v1";
+
+/// Evaluations of several polynomials at the same point, along with the single proof of all of them
+#[derive(Debug, Clone)]
+pub struct SamePointOpening {
+    pub evaluations: Vec<Evaluation>,
+    pub proof: G1Point,
+}
+
+/// Derives the challenge `γ` combining the polynomials opened at the same point
+///
+/// * `commitments` - Commitments of the polynomials, in the order of the batch
+/// * `z` - Input point
+/// * `evaluations` - Evaluations of the polynomials at `z`
+pub fn same_point_challenge(commitments: &[G1Point], z: &Scalar, evaluations: &[Scalar]) -> Scalar {
+    let mut transcript = Transcript::new(SAME_POINT_PROTOCOL);
+    transcript.absorb_scalar(b"z", z);
+    transcript.absorb_bytes(b"size", &(commitments.len() as u64).to_be_bytes());
+    for (commitment, evaluation) in commitments.iter().zip(evaluations) {
+        transcript.absorb_g1_point(b"commitment", commitment);
+        transcript.absorb_scalar(b"evaluation", evaluation);
+    }
+    transcript.squeeze_scalar(b"gamma")
+}
+
+/// Opens several polynomials at the same point with a single proof
+///
+/// * `polynomials` - Opened polynomials, there must be at least one
+/// * `z` - Input point
+/// * `gamma` - Challenge combining the polynomials, it must be derived with `same_point_challenge` for the proof to be verified
+/// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least the largest degree of the polynomials.
+pub fn batch_open_same_point(
+    polynomials: &[Polynomial],
+    z: &Scalar,
+    gamma: &Scalar,
+    setup_artifacts: &[SetupArtifact],
+) -> Result<SamePointOpening, anyhow::Error> {
+    if polynomials.is_empty() {
+        return Err(anyhow::anyhow!("At least one polynomial must be opened"));
+    }
+    let evaluations = polynomials
+        .iter()
+        .map(|polynomial| polynomial.evaluate(z.clone()))
+        .collect::<Result<Vec<Evaluation>, _>>()?;
+
+    let results = evaluations
+        .iter()
+        .map(|e| e.result.clone())
+        .collect::<Vec<Scalar>>();
+    let mut combined = Polynomial::from_constant(Scalar::from_i128(0));
+    for polynomial in polynomials.iter().rev() {
+        combined = combined.scale(gamma).add(polynomial)?;
+    }
+    let proof = Evaluation {
+        point: z.clone(),
+        result: combine_scalars(&results, gamma),
+    }
+    .generate_proof(&combined, setup_artifacts)?;
+
+    Ok(SamePointOpening { evaluations, proof })
+}
+
+/// Verifies the single proof of the openings of several polynomials at the same point
+///
+/// * `commitments` - Commitments of the polynomials, in the order of the batch
+/// * `evaluations` - Evaluations of the polynomials at `z`, in the same order
+/// * `z` - Input point
+/// * `proof` - Proof of all the evaluations
+/// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least 2 artifacts.
+pub fn verify_batch_same_point(
+    commitments: &[G1Point],
+    evaluations: &[Scalar],
+    z: &Scalar,
+    proof: &G1Point,
+    setup_artifacts: &[SetupArtifact],
+) -> Result<bool, anyhow::Error> {
+    if commitments.is_empty() {
+        return Err(anyhow::anyhow!("At least one commitment must be verified"));
+    }
+    if commitments.len() != evaluations.len() {
+        return Err(anyhow::anyhow!(
+            "Got {} commitments but {} evaluations",
+            commitments.len(),
+            evaluations.len()
+        ));
+    }
+    let gamma = same_point_challenge(commitments, z, evaluations);
+
+    let mut commitment = G1Point::from_i128(0);
+    for c in commitments.iter().rev() {
+        commitment = commitment.mult(&gamma).add(c);
+    }
+    let evaluation = Evaluation {
+        point: z.clone(),
+        result: combine_scalars(evaluations, &gamma),
+    };
+    evaluation.verify_proof(proof, &commitment, setup_artifacts)
+}
+
+/// Returns `Σ γ^i y_i` with Horner's rule
+fn combine_scalars(values: &[Scalar], gamma: &Scalar) -> Scalar {
+    values
+        .iter()
+        .rev()
+        .fold(Scalar::from_i128(0), |acc, y| acc.mul(gamma).add(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::ParallelSetupArtifactsGenerator;
+
+    #[test]
+    fn test_batch_open_same_point() {
+        let artifacts = ParallelSetupArtifactsGenerator::new([5; 32]).generate(6);
+        let polynomials = vec![
+            Polynomial::try_from(vec![1, 2, 3]).unwrap(),
+            Polynomial::try_from(vec![-4, 0, 0, 0, 1]).unwrap(),
+            Polynomial::try_from(vec![7]).unwrap(),
+        ];
+        let commitments = polynomials
+            .iter()
+            .map(|p| p.commit(&artifacts).unwrap())
+            .collect::<Vec<G1Point>>();
+        let z = Scalar::from_i128(3);
+        let results = polynomials
+            .iter()
+            .map(|p| p.evaluate(z.clone()).unwrap().result)
+            .collect::<Vec<Scalar>>();
+
+        let gamma = same_point_challenge(&commitments, &z, &results);
+        let opening = batch_open_same_point(&polynomials, &z, &gamma, &artifacts).unwrap();
+        assert_eq!(
+            opening
+                .evaluations
+                .iter()
+                .map(|e| e.result.clone())
+                .collect::<Vec<Scalar>>(),
+            vec![
+                Scalar::from_i128(34),
+                Scalar::from_i128(77),
+                Scalar::from_i128(7)
+            ]
+        );
+        assert!(
+            verify_batch_same_point(&commitments, &results, &z, &opening.proof, &artifacts)
+                .unwrap()
+        );
+
+        // A wrong evaluation, swapped commitments or another point are rejected
+        let mut wrong = results.clone();
+        wrong[1] = Scalar::from_i128(78);
+        assert!(
+            !verify_batch_same_point(&commitments, &wrong, &z, &opening.proof, &artifacts).unwrap()
+        );
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(
+            !verify_batch_same_point(&swapped, &results, &z, &opening.proof, &artifacts).unwrap()
+        );
+        assert!(
+            !verify_batch_same_point(
+                &commitments,
+                &results,
+                &Scalar::from_i128(4),
+                &opening.proof,
+                &artifacts
+            )
+            .unwrap()
+        );
+
+        // The proof only holds for the challenge derived from the transcript
+        let other =
+            batch_open_same_point(&polynomials, &z, &Scalar::from_i128(2), &artifacts).unwrap();
+        assert!(
+            !verify_batch_same_point(&commitments, &results, &z, &other.proof, &artifacts).unwrap()
+        );
+        assert!(
+            verify_batch_same_point(&commitments, &results[..2], &z, &opening.proof, &artifacts)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Proofs that a committed polynomial has a degree lower or equal to a bound `d`.
+//!
+//! With `D` the maximum degree of the setup, the proof is the commitment `[s^(D - d) p(s)]_1` to the shifted polynomial
+//! `x^(D - d) p(x)`. It only exists if `p` has a degree lower or equal to `d`, otherwise the shifted polynomial has a degree
+//! larger than `D` and can not be committed with the setup. The verifier checks `e(C, [s^(D - d)]_2) = e(proof, G2)`.
+use serde::{Deserialize, Serialize};
+
+use super::Polynomial;
+use crate::{
+    curves::{G1Point, G2Point, bilinear_map},
+    scalar::Scalar,
+    trusted_setup::Setup,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegreeBoundProof {
+    /// Bound `d` on the degree of the committed polynomial
+    pub bound: u32,
+    /// Commitment to `x^(D - d) p(x)`
+    pub proof: G1Point,
+}
+
+impl Polynomial {
+    /// Proves that the degree of the polynomial is lower or equal to a bound
+    ///
+    /// * `bound` - Bound on the degree, at most the maximum degree of the setup
+    /// * `setup` - Setup of the commitment, its G2 points must cover the powers up to its maximum degree
+    pub fn prove_degree_bound(
+        &self,
+        bound: u32,
+        setup: &Setup,
+    ) -> Result<DegreeBoundProof, anyhow::Error> {
+        let shift = shift(bound, setup)?;
+        if self.degree() > bound {
+            return Err(anyhow::anyhow!(
+                "The polynomial has degree {}, larger than the bound {bound}",
+                self.degree()
+            ));
+        }
+
+        let mut coefficients = vec![Scalar::from_i128(0); shift];
+        coefficients.extend(self.coefficients.iter().cloned());
+        let proof = Polynomial::try_from(coefficients)?.commit(&setup.artifacts)?;
+
+        Ok(DegreeBoundProof { bound, proof })
+    }
+}
+
+impl DegreeBoundProof {
+    /// Verifies that the committed polynomial has a degree lower or equal to the bound of the proof
+    ///
+    /// * `commitment` - Commitment of the polynomial
+    /// * `setup` - Setup of the commitment
+    pub fn verify(&self, commitment: &G1Point, setup: &Setup) -> Result<bool, anyhow::Error> {
+        let shift = shift(self.bound, setup)?;
+
+        let lhs = bilinear_map(commitment, &setup.artifacts[shift].g2);
+        let rhs = bilinear_map(&self.proof, &G2Point::from_i128(1));
+
+        Ok(lhs == rhs)
+    }
+}
+
+/// Returns the shift `D - d` of a bound, `D` being the maximum degree of the setup
+fn shift(bound: u32, setup: &Setup) -> Result<usize, anyhow::Error> {
+    let max_degree = setup.max_degree();
+    if bound > max_degree {
+        return Err(anyhow::anyhow!(
+            "The degree bound {bound} is larger than the maximum degree {max_degree} of the setup"
+        ));
+    }
+    Ok((max_degree - bound) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator};
+
+    #[test]
+    fn test_degree_bound_proof() {
+        let setup = Setup::new(
+            ParallelSetupArtifactsGenerator::new([4; 32]).generate(6),
+            GenerationMode::RandomSecret,
+        )
+        .unwrap();
+        let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+        let commitment = polynomial.commit(&setup.artifacts).unwrap();
+
+        for bound in [2, 3, 5] {
+            let proof = polynomial.prove_degree_bound(bound, &setup).unwrap();
+            assert!(proof.verify(&commitment, &setup).unwrap());
+        }
+        assert!(polynomial.prove_degree_bound(1, &setup).is_err());
+        assert!(polynomial.prove_degree_bound(6, &setup).is_err());
+
+        // A proof for a degree 3 polynomial does not hold for a lower bound
+        let cubic = Polynomial::try_from(vec![1, 2, 3, 4]).unwrap();
+        let cubic_commitment = cubic.commit(&setup.artifacts).unwrap();
+        let proof = cubic.prove_degree_bound(3, &setup).unwrap();
+        assert!(proof.verify(&cubic_commitment, &setup).unwrap());
+        let forged = DegreeBoundProof { bound: 2, ..proof };
+        assert!(!forged.verify(&cubic_commitment, &setup).unwrap());
+    }
+}
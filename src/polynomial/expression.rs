@@ -0,0 +1,336 @@
+//! Parsing of polynomials written as human expressions, the reverse of the `Display` of `Polynomial`.
+//!
+//! The grammar is:
+//! ```text
+//! expression := ['+' | '-'] term (('+' | '-') term)*
+//! term       := factor (['*'] factor)*
+//! factor     := primary ['^' exponent]
+//! primary    := number | 'x' | '(' expression ')'
+//! ```
+//! Numbers are decimal or `0x` prefixed hexadecimal values reduced modulo r and exponents are decimal integers.
+//! The multiplication sign can be omitted before `x` or a parenthesis, e.g. `3x^2`, `2(x + 1)` or `(x - 1)(x - 2)`.
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::Polynomial;
+use crate::scalar::Scalar;
+
+/// Maximum degree of a polynomial parsed without a bound, it prevents expressions like `x^4294967295` from
+/// exhausting the memory or the CPU, the multiplication of polynomials being quadratic in their degree
+const MAX_DEGREE: u32 = 1 << 12;
+/// Maximum nesting of parentheses, it prevents deeply nested expressions from overflowing the stack of the parser
+const MAX_NESTING: usize = 64;
+
+/// Error of the parsing of a polynomial expression
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("{message} at column {column}")]
+pub struct ExpressionError {
+    /// Column of the error, starting at 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl FromStr for Polynomial {
+    type Err = ExpressionError;
+
+    /// Parses a polynomial expression
+    ///
+    /// * `s` - Expression of the polynomial, e.g. `3x^2 + 2x + 1` or `(x - 1)(x - 2)`
+    ///
+    /// # Examples
+    /// ```rust
+    /// use kzg_poly_commit_exploration::polynomial::Polynomial;
+    ///
+    /// let polynomial: Polynomial = "(x - 1)(x + 1) + 0x2".parse().unwrap();
+    /// assert_eq!(polynomial.to_string(), "1x^2 + 1");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_max_degree(s, MAX_DEGREE)
+    }
+}
+
+impl Polynomial {
+    /// Parses a polynomial expression, rejecting powers and products exceeding a degree before computing them
+    ///
+    /// * `s` - Expression of the polynomial
+    /// * `max_degree` - Maximum degree of the polynomial and of its intermediate results, e.g. the degree of the setup
+    pub fn parse_with_max_degree(s: &str, max_degree: u32) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            position: 0,
+            max_degree: max_degree.into(),
+            nesting: 0,
+        };
+        if parser.peek().is_none() {
+            return Err(parser.error("Empty expression"));
+        }
+        let polynomial = parser.expression()?;
+        match parser.peek() {
+            None => Ok(polynomial),
+            Some(')') => Err(parser.error("Unmatched closing parenthesis")),
+            Some(c) => Err(parser.error(format!("Unexpected character '{c}'"))),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    max_degree: u64,
+    /// Number of parentheses opened around the current position
+    nesting: usize,
+}
+
+impl Parser {
+    /// Returns the next non whitespace character without consuming it
+    fn peek(&mut self) -> Option<char> {
+        while self
+            .chars
+            .get(self.position)
+            .is_some_and(|c| c.is_whitespace())
+        {
+            self.position += 1;
+        }
+        self.chars.get(self.position).copied()
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExpressionError {
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> ExpressionError {
+        ExpressionError {
+            column: position + 1,
+            message: message.into(),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Polynomial, ExpressionError> {
+        let mut polynomial = Polynomial::from(Scalar::from_i128(0));
+        let mut is_negative = match self.peek() {
+            Some(sign @ ('+' | '-')) => {
+                self.position += 1;
+                sign == '-'
+            }
+            _ => false,
+        };
+        loop {
+            let position = self.position;
+            let term = self.term()?;
+            polynomial = if is_negative {
+                polynomial.sub(&term)
+            } else {
+                polynomial.add(&term)
+            }
+            .map_err(|e| self.error_at(position, e.to_string()))?;
+
+            is_negative = match self.peek() {
+                Some('+') => false,
+                Some('-') => true,
+                _ => return Ok(polynomial),
+            };
+            self.position += 1;
+        }
+    }
+
+    fn term(&mut self) -> Result<Polynomial, ExpressionError> {
+        let mut polynomial = self.factor()?;
+        loop {
+            match self.peek() {
+                Some('*') => self.position += 1,
+                Some('x' | '(') => {}
+                _ => return Ok(polynomial),
+            }
+            let position = self.position;
+            let factor = self.factor()?;
+            if polynomial.degree() as u64 + factor.degree() as u64 > self.max_degree {
+                return Err(self.error_at(
+                    position,
+                    format!(
+                        "The expression exceeds the maximum degree {}",
+                        self.max_degree
+                    ),
+                ));
+            }
+            polynomial = polynomial
+                .mul(&factor)
+                .map_err(|e| self.error_at(position, e.to_string()))?;
+        }
+    }
+
+    fn factor(&mut self) -> Result<Polynomial, ExpressionError> {
+        let base = self.primary()?;
+        if self.peek() != Some('^') {
+            return Ok(base);
+        }
+        self.position += 1;
+        self.peek();
+        let start = self.position;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(self.error("Expected an exponent"));
+        }
+        let exponent = digits
+            .parse::<u32>()
+            .map_err(|_| self.error_at(start, format!("Exponent {digits} is too large")))?;
+        if base.degree() as u64 * exponent as u64 > self.max_degree {
+            return Err(self.error_at(
+                start,
+                format!(
+                    "Exponent {digits} exceeds the maximum degree {}",
+                    self.max_degree
+                ),
+            ));
+        }
+        self.pow(&base, exponent, start)
+    }
+
+    fn primary(&mut self) -> Result<Polynomial, ExpressionError> {
+        match self.peek() {
+            Some('x') => {
+                self.position += 1;
+                Ok(Polynomial {
+                    coefficients: vec![Scalar::from_i128(0), Scalar::from_i128(1)],
+                })
+            }
+            Some('(') => {
+                let opening = self.position;
+                if self.nesting == MAX_NESTING {
+                    return Err(self.error(format!(
+                        "The expression exceeds the maximum nesting of {MAX_NESTING} parentheses"
+                    )));
+                }
+                self.position += 1;
+                self.nesting += 1;
+                let polynomial = self.expression()?;
+                self.nesting -= 1;
+                match self.peek() {
+                    Some(')') => {
+                        self.position += 1;
+                        Ok(polynomial)
+                    }
+                    Some(c) => Err(self.error(format!("Expected ')' but found '{c}'"))),
+                    None => Err(self.error_at(opening, "Unclosed parenthesis")),
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.position;
+                let is_hexadecimal = c == '0'
+                    && self.chars.get(start + 1) == Some(&'x')
+                    && self
+                        .chars
+                        .get(start + 2)
+                        .is_some_and(|c| c.is_ascii_hexdigit());
+                let number = if is_hexadecimal {
+                    self.position += 2;
+                    format!("0x{}", self.take_while(|c| c.is_ascii_hexdigit()))
+                } else {
+                    self.take_while(|c| c.is_ascii_digit())
+                };
+                let scalar =
+                    Scalar::from_str(&number).map_err(|e| self.error_at(start, e.to_string()))?;
+                Ok(Polynomial::from(scalar))
+            }
+            Some(c) => Err(self.error(format!(
+                "Unexpected character '{c}', expected a number, 'x' or '('"
+            ))),
+            None => Err(self.error("Unexpected end of expression")),
+        }
+    }
+
+    /// Consumes the characters satisfying the predicate, without skipping whitespaces
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.position;
+        while self.chars.get(self.position).is_some_and(|c| predicate(*c)) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    /// Raises a polynomial to a power by square and multiply
+    fn pow(
+        &self,
+        base: &Polynomial,
+        exponent: u32,
+        position: usize,
+    ) -> Result<Polynomial, ExpressionError> {
+        let mut result = Polynomial::from_constant(Scalar::from_i128(1));
+        let mut square = base.clone();
+        let mut remaining = exponent;
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result
+                    .mul(&square)
+                    .map_err(|e| self.error_at(position, e.to_string()))?;
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                square = square
+                    .mul(&square)
+                    .map_err(|e| self.error_at(position, e.to_string()))?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> String {
+        Polynomial::from_str(s).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_parse_expressions() {
+        assert_eq!(parse("3x^2 + 2x + 1"), "3x^2 + 2x + 1");
+        assert_eq!(parse("1 + 2*x + 3 * x ^ 2"), "3x^2 + 2x + 1");
+        assert_eq!(parse("(x-1)(x-2)"), parse("x^2 - 3x + 2"));
+        assert_eq!(parse("2(x + 1)^2 - 2x^2"), "4x + 2");
+        assert_eq!(parse("0x10x + 0xa"), "16x + 10");
+        assert_eq!(parse("-(x - 5) + x"), "5");
+        assert_eq!(parse("x - x"), "0");
+        assert_eq!(parse("x^0"), "1");
+
+        // Display output is parsed back, including negative coefficients printed modulo r
+        let polynomial = Polynomial::try_from(vec![-7, 0, 1, -3]).unwrap();
+        assert_eq!(parse(&polynomial.to_string()), polynomial.to_string());
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_column() {
+        let error = |s: &str| Polynomial::from_str(s).unwrap_err();
+
+        assert_eq!(error("").column, 1);
+        assert_eq!(error("3x^ + 1").column, 5);
+        assert_eq!(error("3y + 1").column, 2);
+        assert_eq!(error("(x - 1").column, 1);
+        assert_eq!(error("x - 1)").column, 6);
+        assert_eq!(error("2 + * x").column, 5);
+        assert_eq!(error("x^99999999999").column, 3);
+        let nested = format!("{}x{}", "(".repeat(100), ")".repeat(100));
+        assert_eq!(error(&nested).column, MAX_NESTING + 1);
+        assert!(Polynomial::from_str(&nested[36..nested.len() - 36]).is_ok());
+        assert_eq!(
+            error("x + 2 3").to_string(),
+            "Unexpected character '3' at column 7"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_max_degree() {
+        let parse = |s: &str| Polynomial::parse_with_max_degree(s, 4);
+
+        assert_eq!(parse("(x + 1)^4").unwrap().degree(), 4);
+        assert_eq!(parse("x^2 x^2").unwrap().degree(), 4);
+        // Rejected before the power or the product is computed
+        assert_eq!(
+            parse("(x + 1)^65536").unwrap_err().to_string(),
+            "Exponent 65536 exceeds the maximum degree 4 at column 9"
+        );
+        assert_eq!(parse("x^3 (x + 1)^2").unwrap_err().column, 5);
+        assert_eq!(parse("2^1000 x").unwrap().degree(), 1);
+    }
+}
@@ -0,0 +1,151 @@
+//! File formats of the values of a polynomial, either its coefficients or its evaluations over a domain.
+//!
+//! The supported formats are:
+//! - JSON: an array of values, each value being an integer or a decimal or hexadecimal string, e.g. `[1, "0x2a", "-3"]`,
+//! - lines: one decimal or hexadecimal value per line, empty lines and lines starting with `#` are ignored,
+//! - binary: raw concatenation of the values, 32 bytes per value in little endian.
+use std::str::FromStr;
+
+use crate::scalar::Scalar;
+
+/// Size in bytes of a value in the binary format
+const BINARY_ELEMENT_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarsFormat {
+    Json,
+    Lines,
+    Binary,
+}
+
+impl ScalarsFormat {
+    /// Detects the format of the content: JSON if it starts with `[`, lines if it is made of parsable text lines, binary otherwise
+    ///
+    /// * `content` - Content of the file
+    pub fn detect(content: &[u8]) -> Self {
+        if content.trim_ascii_start().starts_with(b"[") {
+            return Self::Json;
+        }
+        match std::str::from_utf8(content) {
+            Ok(text) if text_lines(text).all(|(_, line)| Scalar::from_str(line).is_ok()) => {
+                Self::Lines
+            }
+            _ => Self::Binary,
+        }
+    }
+}
+
+/// Reads the values of a polynomial
+///
+/// * `content` - Content of the file
+/// * `format` - Format of the content, detected from the content if not given
+/// * `strict` - Whether the values that are not canonical field elements must be rejected instead of being reduced modulo r
+pub fn read_scalars(
+    content: &[u8],
+    format: Option<ScalarsFormat>,
+    strict: bool,
+) -> Result<Vec<Scalar>, anyhow::Error> {
+    let parse = |value: &str| {
+        if strict {
+            Scalar::from_str_strict(value)
+        } else {
+            Scalar::from_str(value)
+        }
+    };
+
+    let scalars = match format.unwrap_or_else(|| ScalarsFormat::detect(content)) {
+        ScalarsFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_slice(content)
+                .map_err(|e| anyhow::anyhow!("Invalid JSON array of values: {e}"))?;
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let parsed = match value {
+                        serde_json::Value::String(s) => parse(s),
+                        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => {
+                            parse(&n.to_string())
+                        }
+                        _ => Err(anyhow::anyhow!(
+                            "expected an integer or a string, got {value}"
+                        )),
+                    };
+                    parsed.map_err(|e| anyhow::anyhow!("Invalid value at index {i}: {e}"))
+                })
+                .collect::<Result<Vec<Scalar>, _>>()?
+        }
+        ScalarsFormat::Lines => {
+            let text = std::str::from_utf8(content)
+                .map_err(|e| anyhow::anyhow!("Values must be valid UTF-8 text: {e}"))?;
+            text_lines(text)
+                .map(|(line_number, line)| {
+                    parse(line)
+                        .map_err(|e| anyhow::anyhow!("Invalid value at line {line_number}: {e}"))
+                })
+                .collect::<Result<Vec<Scalar>, _>>()?
+        }
+        ScalarsFormat::Binary => {
+            if !content.len().is_multiple_of(BINARY_ELEMENT_SIZE) {
+                return Err(anyhow::anyhow!(
+                    "Invalid binary values, the length must be a multiple of {BINARY_ELEMENT_SIZE} bytes, got {} bytes",
+                    content.len()
+                ));
+            }
+            content
+                .chunks(BINARY_ELEMENT_SIZE)
+                .enumerate()
+                .map(|(i, le_bytes)| {
+                    let be_bytes: Vec<u8> = le_bytes.iter().rev().copied().collect();
+                    parse(&format!("0x{}", hex::encode(be_bytes)))
+                        .map_err(|e| anyhow::anyhow!("Invalid value at index {i}: {e}"))
+                })
+                .collect::<Result<Vec<Scalar>, _>>()?
+        }
+    };
+
+    if scalars.is_empty() {
+        return Err(anyhow::anyhow!("No values found"));
+    }
+    Ok(scalars)
+}
+
+/// Returns the lines holding a value along with their line number, starting from 1
+fn text_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_scalars_in_every_format() {
+        let expected: Vec<Scalar> = [1, 42, -3].into_iter().map(Scalar::from_i128).collect();
+
+        let json = br#"[1, "0x2a", "-3"]"#;
+        let lines = b"# coefficients\n1\n0x2a\n\n-3\n";
+        let binary: Vec<u8> = expected.iter().flat_map(|s| s.to_le_bytes()).collect();
+
+        for (content, format) in [
+            (json.as_slice(), ScalarsFormat::Json),
+            (lines.as_slice(), ScalarsFormat::Lines),
+            (binary.as_slice(), ScalarsFormat::Binary),
+        ] {
+            assert_eq!(ScalarsFormat::detect(content), format);
+            assert_eq!(read_scalars(content, None, false).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid_scalars() {
+        assert!(read_scalars(b"[1, 2.5]", None, false).is_err());
+        assert!(read_scalars(b"[]", None, false).is_err());
+        assert!(read_scalars(&[1u8; 33], Some(ScalarsFormat::Binary), false).is_err());
+        assert!(read_scalars(b"1\n-3\n", None, true).is_err());
+        assert!(read_scalars(&[0xffu8; 32], None, true).is_err());
+        assert!(read_scalars(&[0xffu8; 32], None, false).is_ok());
+    }
+}
@@ -0,0 +1,206 @@
+//! Proofs of the evaluations of a polynomial at all the points of a domain of roots of unity, following Feist and
+//! Khovratovich (FK20), in `O(n log n)` group operations instead of `O(n^2)` for `n` separate proofs.
+//!
+//! The quotient of `f = Σ f_j x^j` by `x^l - α` commits to `Σ_u α^u H_u` with `H_u = Σ_k f_{k + (u + 1) l} [s^k]_1`.
+//! Splitting `k` by its residue modulo `l`, the `H_u` are sums of `l` Toeplitz matrix-vector products between the
+//! coefficients and the setup points, each computed as a convolution with FFTs over G1. The cosets of size `l` of a
+//! domain of size `n` are the roots of `x^l - α` for `α` the `n / l`-th roots of unity, so the proofs of all the
+//! cosets are the FFT of the `H_u` over G1. Proofs of single points are the case `l = 1`.
+use super::{Evaluation, MultiPointEvaluation, Polynomial};
+use crate::{curves::G1Point, domain::Domain, scalar::Scalar, trusted_setup::SetupArtifact};
+
+impl Polynomial {
+    /// Computes the evaluations of the polynomial at all the points of the domain along with their proofs
+    ///
+    /// * `domain` - Domain of roots of unity, the evaluations are in the order of its elements
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `degree + 1` artifacts.
+    pub fn prove_all_points(
+        &self,
+        domain: &Domain,
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<Vec<(Evaluation, G1Point)>, anyhow::Error> {
+        Ok(self
+            .prove_all_cosets(domain, 1, setup_artifacts)?
+            .into_iter()
+            .map(|(mut evaluation, proof)| (evaluation.evaluations.remove(0), proof))
+            .collect())
+    }
+
+    /// Computes the evaluations of the polynomial on all the cosets of size `l` of the domain along with their proofs
+    ///
+    /// The `i`-th coset is made of the points `ω^(i + j n / l)` for `j` from `0` to `l - 1`, its proof is verified
+    /// with `MultiPointEvaluation::verify_proof`.
+    ///
+    /// * `domain` - Domain of roots of unity of size `n`
+    /// * `coset_size` - Size `l` of the cosets, a power of two up to `n`
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least `degree + 1` artifacts.
+    pub fn prove_all_cosets(
+        &self,
+        domain: &Domain,
+        coset_size: usize,
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<Vec<(MultiPointEvaluation, G1Point)>, anyhow::Error> {
+        let n = domain.size();
+        if !coset_size.is_power_of_two() || coset_size > n {
+            return Err(anyhow::anyhow!(
+                "The size of the cosets must be a power of two up to the size {n} of the domain, got {coset_size}"
+            ));
+        }
+        let length = self.coefficients.len();
+        if length > setup_artifacts.len() {
+            return Err(anyhow::anyhow!(
+                "Setup does not allow for proof generation of the polynomial. The polynomial of degree {} requires {} setup artifacts, got {}.",
+                self.degree(),
+                self.degree() + 1,
+                setup_artifacts.len()
+            ));
+        }
+        let coset_count = n / coset_size;
+
+        // H_u as the sum of the Toeplitz products of the coefficients and points of each residue modulo l,
+        // folded modulo the number of cosets as α^(n / l) = 1
+        let mut quotients = vec![G1Point::from_i128(0); coset_count];
+        for residue in 0..coset_size.min(length) {
+            let coefficients = self
+                .coefficients
+                .iter()
+                .skip(residue)
+                .step_by(coset_size)
+                .cloned()
+                .collect::<Vec<Scalar>>();
+            let points = setup_artifacts[..length]
+                .iter()
+                .skip(residue)
+                .step_by(coset_size)
+                .map(|artifact| artifact.g1.clone())
+                .collect::<Vec<G1Point>>();
+            for (u, product) in toeplitz_product(&coefficients, &points)?.iter().enumerate() {
+                quotients[u % coset_count] = quotients[u % coset_count].add(product);
+            }
+        }
+        let proofs = Domain::new(coset_count)?.fft(&quotients)?;
+
+        // Evaluations over the whole domain, with the coefficients folded modulo n as ω^n = 1
+        let mut folded = vec![Scalar::from_i128(0); n];
+        for (j, coefficient) in self.coefficients.iter().enumerate() {
+            folded[j % n] = folded[j % n].add(coefficient);
+        }
+        let results = domain.fft(&folded)?;
+        let elements = domain.elements();
+
+        Ok(proofs
+            .into_iter()
+            .enumerate()
+            .map(|(i, proof)| {
+                let evaluations = (0..coset_size)
+                    .map(|j| Evaluation {
+                        point: elements[i + j * coset_count].clone(),
+                        result: results[i + j * coset_count].clone(),
+                    })
+                    .collect();
+                (MultiPointEvaluation { evaluations }, proof)
+            })
+            .collect())
+    }
+}
+
+/// Computes `h_k = Σ_m c_(k + 1 + m) P_m` for `k` from `0` to `d - 1`, `d + 1` being the number of coefficients.
+///
+/// With `P'_t = P_(d - 1 - t)`, `h_k` is the coefficient `d + k` of the convolution of `c` and `P'`, computed with
+/// FFTs of size at least `2d` so that the cyclic convolution does not wrap around.
+///
+/// * `coefficients` - Coefficients `c_0, ..., c_d`
+/// * `points` - Points `P_0, ..., P_(d - 1)`, there must be at least `d` of them
+fn toeplitz_product(
+    coefficients: &[Scalar],
+    points: &[G1Point],
+) -> Result<Vec<G1Point>, anyhow::Error> {
+    let d = coefficients.len().saturating_sub(1);
+    if d == 0 {
+        return Ok(vec![]);
+    }
+    let size = (2 * d).next_power_of_two();
+    let domain = Domain::new(size)?;
+
+    let mut padded_coefficients = coefficients.to_vec();
+    padded_coefficients.resize(size, Scalar::from_i128(0));
+    let mut reversed_points = points[..d].iter().rev().cloned().collect::<Vec<G1Point>>();
+    reversed_points.resize(size, G1Point::from_i128(0));
+
+    let products = domain
+        .fft(&reversed_points)?
+        .iter()
+        .zip(domain.fft(&padded_coefficients)?)
+        .map(|(point, coefficient)| point.mult(&coefficient))
+        .collect::<Vec<G1Point>>();
+    Ok(domain.ifft(&products)?[d..2 * d].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::ParallelSetupArtifactsGenerator;
+
+    #[test]
+    fn test_prove_all_points_matches_single_proofs() {
+        let artifacts = ParallelSetupArtifactsGenerator::new([3; 32]).generate(8);
+        let polynomial = Polynomial::try_from(vec![5, -1, 0, 2, 7, 0, 1, 3]).unwrap();
+        let commitment = polynomial.commit(&artifacts).unwrap();
+
+        // Domains larger and smaller than the number of coefficients
+        for size in [16, 4] {
+            let domain = Domain::new(size).unwrap();
+            let openings = polynomial.prove_all_points(&domain, &artifacts).unwrap();
+            assert_eq!(openings.len(), size);
+            for ((evaluation, proof), point) in openings.iter().zip(domain.elements()) {
+                assert_eq!(evaluation.point, point);
+                assert_eq!(
+                    evaluation.result,
+                    polynomial.evaluate(point.clone()).unwrap().result
+                );
+                assert_eq!(
+                    proof,
+                    &evaluation.generate_proof(&polynomial, &artifacts).unwrap()
+                );
+                assert!(
+                    evaluation
+                        .verify_proof(proof, &commitment, &artifacts)
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_prove_all_cosets() {
+        let artifacts = ParallelSetupArtifactsGenerator::new([3; 32]).generate(8);
+        let polynomial = Polynomial::try_from(vec![5, -1, 0, 2, 7, 0, 1, 3]).unwrap();
+        let commitment = polynomial.commit(&artifacts).unwrap();
+        let domain = Domain::new(8).unwrap();
+
+        for coset_size in [2, 4] {
+            let openings = polynomial
+                .prove_all_cosets(&domain, coset_size, &artifacts)
+                .unwrap();
+            assert_eq!(openings.len(), 8 / coset_size);
+            for (evaluation, proof) in &openings {
+                assert_eq!(evaluation.evaluations.len(), coset_size);
+                assert_eq!(
+                    proof,
+                    &evaluation.generate_proof(&polynomial, &artifacts).unwrap()
+                );
+                assert!(
+                    evaluation
+                        .verify_proof(proof, &commitment, &artifacts)
+                        .unwrap()
+                );
+            }
+        }
+        assert!(polynomial.prove_all_cosets(&domain, 3, &artifacts).is_err());
+        assert!(
+            polynomial
+                .prove_all_cosets(&domain, 16, &artifacts)
+                .is_err()
+        );
+    }
+}
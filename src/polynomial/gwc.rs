@@ -0,0 +1,237 @@
+//! Batched openings of several polynomials at several points, following Gabizon, Williamson and Ciobotaru (GWC19,
+//! section 3 of the PLONK paper).
+//!
+//! The openings are grouped by point. With a challenge `γ`, the polynomials opened at the same point `z_j` are combined
+//! into `p_j = Σ γ^k f_k` and opened with a single proof `W_j`, see the `batch` module. With a second challenge `r`,
+//! the verifier checks all the proofs at once with a single multi-pairing:
+//! `e(Σ r^j (C_j - [y_j]_1 + z_j W_j), G2) = e(Σ r^j W_j, [s]_2)`, `C_j` and `y_j` being the combined commitment and
+//! evaluation of the group. The proof has one point per distinct opening point.
+use super::{
+    MultiPointEvaluation, Polynomial, batch::batch_open_same_point, batched_openings_transcript,
+    check_batched_openings,
+};
+use crate::{
+    curves::{G1Point, G2Point, multi_pairing_is_one},
+    scalar::Scalar,
+    trusted_setup::SetupArtifact,
+};
+
+const GWC_PROTOCOL: &[u8] = b"kzg-poly-commit-exploration/gwc/v1";
+
+/// Proof of the openings of several polynomials at several points, with one point per distinct opening point
+#[derive(Debug, Clone)]
+pub struct GwcProof {
+    /// Proofs of the groups, in the order of the first appearance of their point in the evaluations
+    pub proofs: Vec<G1Point>,
+}
+
+/// Openings at the same point, given by the index of the polynomial and its evaluation
+struct Group {
+    point: Scalar,
+    openings: Vec<(usize, Scalar)>,
+}
+
+impl GwcProof {
+    /// Generates the proof of the evaluations of the polynomials
+    ///
+    /// * `polynomials` - Opened polynomials
+    /// * `evaluations` - Evaluations of each polynomial on its own set of distinct points, in the same order
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least the largest degree of the polynomials.
+    pub fn generate(
+        polynomials: &[Polynomial],
+        evaluations: &[MultiPointEvaluation],
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<Self, anyhow::Error> {
+        check_batched_openings(polynomials.len(), evaluations)?;
+        let commitments = polynomials
+            .iter()
+            .map(|polynomial| polynomial.commit(setup_artifacts))
+            .collect::<Result<Vec<G1Point>, _>>()?;
+        let gamma = batched_openings_transcript(GWC_PROTOCOL, &commitments, evaluations)
+            .squeeze_scalar(b"gamma");
+
+        let mut proofs = vec![];
+        for group in groups(evaluations) {
+            let group_polynomials = group
+                .openings
+                .iter()
+                .map(|(i, _)| polynomials[*i].clone())
+                .collect::<Vec<Polynomial>>();
+            let opening =
+                batch_open_same_point(&group_polynomials, &group.point, &gamma, setup_artifacts)?;
+            if opening
+                .evaluations
+                .iter()
+                .zip(&group.openings)
+                .any(|(evaluation, (_, result))| evaluation.result != *result)
+            {
+                return Err(anyhow::anyhow!(
+                    "The evaluations do not match the polynomials at {}, unable to generate the proof",
+                    group.point
+                ));
+            }
+            proofs.push(opening.proof);
+        }
+
+        Ok(Self { proofs })
+    }
+
+    /// Verifies the proof of the evaluations against the commitments of the polynomials
+    ///
+    /// * `commitments` - Commitments of the polynomials
+    /// * `evaluations` - Evaluations of each polynomial on its own set of distinct points, in the same order
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least 2 artifacts.
+    pub fn verify(
+        &self,
+        commitments: &[G1Point],
+        evaluations: &[MultiPointEvaluation],
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<bool, anyhow::Error> {
+        check_batched_openings(commitments.len(), evaluations)?;
+        if setup_artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "The verification requires at least 2 setup artifacts"
+            ));
+        }
+        let groups = groups(evaluations);
+        if groups.len() != self.proofs.len() {
+            return Err(anyhow::anyhow!(
+                "Got {} proofs for {} distinct points",
+                self.proofs.len(),
+                groups.len()
+            ));
+        }
+
+        let mut transcript = batched_openings_transcript(GWC_PROTOCOL, commitments, evaluations);
+        let gamma = transcript.squeeze_scalar(b"gamma");
+        for proof in &self.proofs {
+            transcript.absorb_g1_point(b"proof", proof);
+        }
+        let r = transcript.squeeze_scalar(b"r");
+
+        // Σ r^j (C_j - [y_j]_1 + z_j W_j) and Σ r^j W_j, with Horner's rule
+        let mut lhs = G1Point::from_i128(0);
+        let mut rhs = G1Point::from_i128(0);
+        for (group, proof) in groups.iter().zip(&self.proofs).rev() {
+            let mut commitment = G1Point::from_i128(0);
+            let mut result = Scalar::from_i128(0);
+            for (i, y) in group.openings.iter().rev() {
+                commitment = commitment.mult(&gamma).add(&commitments[*i]);
+                result = result.mul(&gamma).add(y);
+            }
+            let term = commitment
+                .sub(&G1Point::from_scalar(result))
+                .add(&proof.mult(&group.point));
+            lhs = lhs.mult(&r).add(&term);
+            rhs = rhs.mult(&r).add(proof);
+        }
+
+        // e(Σ r^j (C_j - [y_j]_1 + z_j W_j), G2) e(-Σ r^j W_j, [s]_2) = 1
+        Ok(multi_pairing_is_one(&[
+            (&lhs, &G2Point::from_i128(1)),
+            (&G1Point::from_i128(0).sub(&rhs), &setup_artifacts[1].g2),
+        ]))
+    }
+}
+
+/// Groups the openings by point, in the order of the first appearance of the points
+fn groups(evaluations: &[MultiPointEvaluation]) -> Vec<Group> {
+    let mut groups: Vec<Group> = vec![];
+    for (i, evaluation) in evaluations.iter().enumerate() {
+        for e in &evaluation.evaluations {
+            let opening = (i, e.result.clone());
+            match groups.iter_mut().find(|group| group.point == e.point) {
+                Some(group) => group.openings.push(opening),
+                None => groups.push(Group {
+                    point: e.point.clone(),
+                    openings: vec![opening],
+                }),
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::ParallelSetupArtifactsGenerator;
+
+    #[test]
+    fn test_gwc_opening() {
+        let artifacts = ParallelSetupArtifactsGenerator::new([6; 32]).generate(8);
+        let polynomials = vec![
+            Polynomial::try_from(vec![1, 2, 3]).unwrap(),
+            Polynomial::try_from(vec![-4, 0, 0, 0, 1]).unwrap(),
+            Polynomial::try_from(vec![5, -1, 0, 2, 0, 0, 1]).unwrap(),
+        ];
+        let commitments = polynomials
+            .iter()
+            .map(|p| p.commit(&artifacts).unwrap())
+            .collect::<Vec<G1Point>>();
+        // Every polynomial is opened at 1 and -2, in any order, and only the last one at 7
+        let evaluations = [vec![1, -2], vec![-2, 1], vec![7, 1, -2]]
+            .iter()
+            .zip(&polynomials)
+            .map(|(points, p)| {
+                let points = points
+                    .iter()
+                    .map(|v| Scalar::from_i128(*v))
+                    .collect::<Vec<Scalar>>();
+                MultiPointEvaluation::new(p, &points).unwrap()
+            })
+            .collect::<Vec<MultiPointEvaluation>>();
+
+        let groups = groups(&evaluations);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| (group.point.clone(), group.openings.len()))
+                .collect::<Vec<(Scalar, usize)>>(),
+            vec![
+                (Scalar::from_i128(1), 3),
+                (Scalar::from_i128(-2), 3),
+                (Scalar::from_i128(7), 1)
+            ]
+        );
+
+        // One proof per distinct point, each combining all the polynomials opened there
+        let proof = GwcProof::generate(&polynomials, &evaluations, &artifacts).unwrap();
+        assert_eq!(proof.proofs.len(), 3);
+        assert!(
+            proof
+                .verify(&commitments, &evaluations, &artifacts)
+                .unwrap()
+        );
+
+        // A wrong evaluation in a shared group is rejected by the verifier and can not be proven
+        let mut wrong = evaluations.clone();
+        wrong[1].evaluations[1].result = wrong[1].evaluations[1].result.add(&Scalar::from_i128(1));
+        assert!(!proof.verify(&commitments, &wrong, &artifacts).unwrap());
+        assert!(GwcProof::generate(&polynomials, &wrong, &artifacts).is_err());
+
+        let mut swapped = proof.clone();
+        swapped.proofs.swap(0, 1);
+        assert!(
+            !swapped
+                .verify(&commitments, &evaluations, &artifacts)
+                .unwrap()
+        );
+        swapped.proofs.pop();
+        assert!(
+            swapped
+                .verify(&commitments, &evaluations, &artifacts)
+                .is_err()
+        );
+
+        // Empty sets and repeated points are rejected as by Shplonk
+        let mut empty = evaluations.clone();
+        empty[0].evaluations.clear();
+        assert!(GwcProof::generate(&polynomials, &empty, &artifacts).is_err());
+        assert!(proof.verify(&commitments, &empty, &artifacts).is_err());
+        let mut repeated = evaluations.clone();
+        repeated[2].evaluations[1] = repeated[2].evaluations[2].clone();
+        assert!(GwcProof::generate(&polynomials, &repeated, &artifacts).is_err());
+        assert!(proof.verify(&commitments, &repeated, &artifacts).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Hiding variant of the commitments, in which a random blinding polynomial `r` masks the committed polynomial `p`.
+//!
+//! With `H = [γ]_1` and the setup points `[γ s^i]_1`, the commitment is `C = [p(s)]_1 + [γ r(s)]_1`.
+//! An opening at `z` reveals `y = p(z)` and the blinding evaluation `ŷ = r(z)`, the proof is
+//! `W = [q(s)]_1 + [γ q̂(s)]_1` with `q = (p - y) / (x - z)` and `q̂ = (r - ŷ) / (x - z)`,
+//! and the verifier checks `e(C - [y]_1 - ŷ H, G2) = e(W, [s - z]_2)`.
+use rand::RngCore;
+
+use super::Polynomial;
+use crate::{
+    curves::{G1Point, G2Point, bilinear_map},
+    scalar::Scalar,
+    trusted_setup::Setup,
+};
+
+/// Evaluation of a polynomial committed with a hiding commitment, along with the evaluation of its blinding polynomial
+#[derive(Debug, Clone)]
+pub struct HidingEvaluation {
+    pub point: Scalar,
+    pub result: Scalar,
+    pub blinding_result: Scalar,
+}
+
+impl Polynomial {
+    /// Creates a polynomial with random coefficients, used as blinding polynomial of a hiding commitment
+    ///
+    /// * `degree` - Degree of the polynomial
+    pub fn random(degree: u32) -> Self {
+        let coefficients = (0..=degree)
+            .map(|_| {
+                // 64 random bytes are reduced modulo r so that the bias of the reduction is negligible
+                let mut bytes = [0; 64];
+                rand::rng().fill_bytes(&mut bytes);
+                Scalar::from_be_bytes_wide(&bytes)
+            })
+            .collect();
+        Polynomial { coefficients }
+    }
+
+    /// Generates the hiding commitment `[p(s)]_1 + [γ r(s)]_1` of the polynomial
+    ///
+    /// * `blinding` - Random blinding polynomial, it must be kept along with the polynomial to open the commitment
+    /// * `setup` - Setup with hiding points, both polynomials must be supported by the setup
+    pub fn commit_hiding(
+        &self,
+        blinding: &Polynomial,
+        setup: &Setup,
+    ) -> Result<G1Point, anyhow::Error> {
+        Ok(self
+            .commit(&setup.artifacts)?
+            .add(&blinding.commit_to_hiding_points(setup)?))
+    }
+
+    /// Evaluates the polynomial and its blinding polynomial at a point
+    ///
+    /// * `blinding` - Blinding polynomial of the hiding commitment
+    /// * `x` - Input point
+    pub fn evaluate_hiding(
+        &self,
+        blinding: &Polynomial,
+        x: Scalar,
+    ) -> Result<HidingEvaluation, anyhow::Error> {
+        Ok(HidingEvaluation {
+            result: self.evaluate(x.clone())?.result,
+            blinding_result: blinding.evaluate(x.clone())?.result,
+            point: x,
+        })
+    }
+
+    /// Commits to the polynomial using the hiding points `[γ s^i]_1` of the setup
+    fn commit_to_hiding_points(&self, setup: &Setup) -> Result<G1Point, anyhow::Error> {
+        if !setup.is_hiding() {
+            return Err(anyhow::anyhow!(
+                "The setup does not contain the points of hiding commitments, generate a hiding setup."
+            ));
+        }
+        if self.coefficients.len() > setup.hiding_g1.len() {
+            return Err(anyhow::anyhow!(
+                "The blinding polynomial of degree {} requires {} hiding points, got {}.",
+                self.degree(),
+                self.degree() + 1,
+                setup.hiding_g1.len()
+            ));
+        }
+        let mut commitment = G1Point::from_i128(0);
+        for (coefficient, point) in self.coefficients.iter().zip(&setup.hiding_g1) {
+            commitment = commitment.add(&point.mult(coefficient));
+        }
+        Ok(commitment)
+    }
+}
+
+impl HidingEvaluation {
+    /// Generates the proof `[q(s)]_1 + [γ q̂(s)]_1` of the evaluation
+    ///
+    /// * `polynomial` - The polynomial associated with the evaluation
+    /// * `blinding` - Blinding polynomial of the hiding commitment
+    /// * `setup` - Setup with hiding points
+    pub fn generate_proof(
+        &self,
+        polynomial: &Polynomial,
+        blinding: &Polynomial,
+        setup: &Setup,
+    ) -> Result<G1Point, anyhow::Error> {
+        let quotient = polynomial
+            .sub(&Polynomial::from_constant(self.result.clone()))?
+            .divide_by_root(&self.point)?;
+        let blinding_quotient = blinding
+            .sub(&Polynomial::from_constant(self.blinding_result.clone()))?
+            .divide_by_root(&self.point)?;
+        Ok(quotient
+            .commit(&setup.artifacts)?
+            .add(&blinding_quotient.commit_to_hiding_points(setup)?))
+    }
+
+    /// Verifies the proof of the evaluation against a hiding commitment
+    ///
+    /// * `proof` - Evaluation proof
+    /// * `commitment` - Hiding commitment of the underlying polynomial
+    /// * `setup` - Setup with hiding points, there must be at least 2 artifacts
+    pub fn verify_proof(
+        &self,
+        proof: &G1Point,
+        commitment: &G1Point,
+        setup: &Setup,
+    ) -> Result<bool, anyhow::Error> {
+        let Some(h) = setup.hiding_g1.first() else {
+            return Err(anyhow::anyhow!(
+                "The setup does not contain the points of hiding commitments, generate a hiding setup."
+            ));
+        };
+        if setup.artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "The verification requires at least 2 setup artifacts"
+            ));
+        }
+
+        let lhs = bilinear_map(
+            &commitment
+                .sub(&G1Point::from_scalar(self.result.clone()))
+                .sub(&h.mult(&self.blinding_result)),
+            &G2Point::from_i128(1),
+        );
+        let rhs = bilinear_map(
+            proof,
+            &setup.artifacts[1]
+                .g2
+                .sub(&G2Point::from_scalar(self.point.clone())),
+        );
+
+        Ok(lhs == rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator};
+
+    #[test]
+    fn test_hiding_commitment_and_opening() {
+        let generator = ParallelSetupArtifactsGenerator::new([9; 32]);
+        let setup = Setup::new(generator.generate(4), GenerationMode::RandomSecret)
+            .unwrap()
+            .with_hiding_powers(generator.generate_hiding_powers(4, [11; 32]))
+            .unwrap();
+        setup.validate().unwrap();
+
+        let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+        let blinding = Polynomial::random(polynomial.degree());
+        let commitment = polynomial.commit_hiding(&blinding, &setup).unwrap();
+
+        // The same polynomial gives different commitments, none of them equal to the binding commitment
+        let other_commitment = polynomial
+            .commit_hiding(&Polynomial::random(polynomial.degree()), &setup)
+            .unwrap();
+        assert_ne!(commitment, other_commitment);
+        assert_ne!(commitment, polynomial.commit(&setup.artifacts).unwrap());
+
+        let evaluation = polynomial
+            .evaluate_hiding(&blinding, Scalar::from_i128(5))
+            .unwrap();
+        assert_eq!(evaluation.result, Scalar::from_i128(86));
+        let proof = evaluation
+            .generate_proof(&polynomial, &blinding, &setup)
+            .unwrap();
+        assert!(
+            evaluation
+                .verify_proof(&proof, &commitment, &setup)
+                .unwrap()
+        );
+        assert!(
+            !evaluation
+                .verify_proof(&proof, &other_commitment, &setup)
+                .unwrap()
+        );
+
+        let wrong_evaluation = HidingEvaluation {
+            result: Scalar::from_i128(87),
+            ..evaluation.clone()
+        };
+        assert!(
+            !wrong_evaluation
+                .verify_proof(&proof, &commitment, &setup)
+                .unwrap()
+        );
+        let wrong_blinding = HidingEvaluation {
+            blinding_result: evaluation.blinding_result.add(&Scalar::from_i128(1)),
+            ..evaluation
+        };
+        assert!(
+            !wrong_blinding
+                .verify_proof(&proof, &commitment, &setup)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hiding_requires_hiding_setup() {
+        let setup = Setup::new(
+            ParallelSetupArtifactsGenerator::new([9; 32]).generate(4),
+            GenerationMode::RandomSecret,
+        )
+        .unwrap();
+        let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+        assert!(
+            polynomial
+                .commit_hiding(&Polynomial::random(2), &setup)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Openings of several polynomials, each at its own set of points, with a constant-size proof, following
+//! Boneh, Drake, Fisch and Gabizon (BDFG20, also known as Shplonk).
+//!
+//! Each polynomial `f_i` is opened on a set `S_i`, `r_i` interpolates its evaluations and `T` is the union of the sets.
+//! With a challenge `γ`, the prover commits to `h = Σ γ^i Z_{T \ S_i} (f_i - r_i) / Z_T` as `W`. With a second
+//! challenge `z`, the polynomial `L = Σ γ^i Z_{T \ S_i}(z) (f_i - r_i(z)) - Z_T(z) h` vanishes at `z` and the prover
+//! commits to `L / (x - z)` as `W'`. The verifier computes `[L(s)]_1` as
+//! `F = Σ γ^i Z_{T \ S_i}(z) (C_i - [r_i(z)]_1) - Z_T(z) W` and checks `e(F + z W', G2) = e(W', [s]_2)`.
+//! Both challenges are derived with the Fiat–Shamir heuristic.
+use super::{
+    MultiPointEvaluation, Polynomial, batched_openings_transcript, check_batched_openings,
+};
+use crate::{
+    curves::{G1Point, G2Point, multi_pairing_is_one},
+    scalar::Scalar,
+    trusted_setup::SetupArtifact,
+};
+
+const SHPLONK_PROTOCOL: &[u8] = b"kzg-poly-commit-exploration/shplonk/v1";
+
+/// Constant-size proof of the openings of several polynomials at several sets of points
+#[derive(Debug, Clone)]
+pub struct ShplonkProof {
+    /// Commitment `W` to the quotient `h` of the combined polynomials by `Z_T`
+    pub quotient: G1Point,
+    /// Commitment `W'` to `L / (x - z)`
+    pub opening: G1Point,
+}
+
+impl ShplonkProof {
+    /// Generates the proof of the evaluations of the polynomials
+    ///
+    /// * `polynomials` - Opened polynomials
+    /// * `evaluations` - Evaluations of each polynomial on its own set of distinct points, in the same order
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least the largest degree of the polynomials.
+    pub fn generate(
+        polynomials: &[Polynomial],
+        evaluations: &[MultiPointEvaluation],
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<Self, anyhow::Error> {
+        check_batched_openings(polynomials.len(), evaluations)?;
+        let commitments = polynomials
+            .iter()
+            .map(|polynomial| polynomial.commit(setup_artifacts))
+            .collect::<Result<Vec<G1Point>, _>>()?;
+        let mut transcript =
+            batched_openings_transcript(SHPLONK_PROTOCOL, &commitments, evaluations);
+        let gamma = transcript.squeeze_scalar(b"gamma");
+
+        let all_points = union(evaluations);
+        let interpolated = evaluations
+            .iter()
+            .map(|e| Polynomial::interpolate(&e.evaluations))
+            .collect::<Result<Vec<Polynomial>, _>>()?;
+
+        // f = Σ γ^i Z_{T \ S_i} (f_i - r_i), with Horner's rule
+        let mut combined = Polynomial::from_constant(Scalar::from_i128(0));
+        for ((polynomial, evaluation), r) in
+            polynomials.iter().zip(evaluations).zip(&interpolated).rev()
+        {
+            let complement = Polynomial::vanishing(&complement(&all_points, evaluation))?;
+            combined = combined
+                .scale(&gamma)
+                .add(&complement.mul(&polynomial.sub(r)?)?)?;
+        }
+        let vanishing = Polynomial::vanishing(&all_points)?;
+        let (quotient, remainder) = combined.divide(&vanishing)?;
+        if !remainder.coefficients.iter().all(Scalar::is_zero) {
+            return Err(anyhow::anyhow!(
+                "The evaluations do not match the polynomials, unable to generate the proof"
+            ));
+        }
+        let quotient_commitment = quotient.commit(setup_artifacts)?;
+
+        transcript.absorb_g1_point(b"quotient", &quotient_commitment);
+        let z = transcript.squeeze_scalar(b"z");
+
+        // L = Σ γ^i Z_{T \ S_i}(z) (f_i - r_i(z)) - Z_T(z) h
+        let mut linearised = Polynomial::from_constant(Scalar::from_i128(0));
+        for ((polynomial, evaluation), r) in
+            polynomials.iter().zip(evaluations).zip(&interpolated).rev()
+        {
+            let factor = Polynomial::vanishing(&complement(&all_points, evaluation))?
+                .evaluate(z.clone())?
+                .result;
+            let shifted =
+                polynomial.sub(&Polynomial::from_constant(r.evaluate(z.clone())?.result))?;
+            linearised = linearised.scale(&gamma).add(&shifted.scale(&factor))?;
+        }
+        let linearised = linearised.sub(&quotient.scale(&vanishing.evaluate(z.clone())?.result))?;
+        let opening = linearised.divide_by_root(&z)?.commit(setup_artifacts)?;
+
+        Ok(Self {
+            quotient: quotient_commitment,
+            opening,
+        })
+    }
+
+    /// Verifies the proof of the evaluations against the commitments of the polynomials
+    ///
+    /// * `commitments` - Commitments of the polynomials
+    /// * `evaluations` - Evaluations of each polynomial on its own set of distinct points, in the same order
+    /// * `setup_artifacts` - List of setup artifacts for both elliptic curve groups. There must at least 2 artifacts.
+    pub fn verify(
+        &self,
+        commitments: &[G1Point],
+        evaluations: &[MultiPointEvaluation],
+        setup_artifacts: &[SetupArtifact],
+    ) -> Result<bool, anyhow::Error> {
+        check_batched_openings(commitments.len(), evaluations)?;
+        if setup_artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "The verification requires at least 2 setup artifacts"
+            ));
+        }
+        let mut transcript =
+            batched_openings_transcript(SHPLONK_PROTOCOL, commitments, evaluations);
+        let gamma = transcript.squeeze_scalar(b"gamma");
+        transcript.absorb_g1_point(b"quotient", &self.quotient);
+        let z = transcript.squeeze_scalar(b"z");
+
+        let all_points = union(evaluations);
+
+        // F = Σ γ^i Z_{T \ S_i}(z) (C_i - [r_i(z)]_1) - Z_T(z) W, with Horner's rule
+        let mut combined = G1Point::from_i128(0);
+        for (commitment, evaluation) in commitments.iter().zip(evaluations).rev() {
+            let factor = Polynomial::vanishing(&complement(&all_points, evaluation))?
+                .evaluate(z.clone())?
+                .result;
+            let r_z = Polynomial::interpolate(&evaluation.evaluations)?
+                .evaluate(z.clone())?
+                .result;
+            combined = combined
+                .mult(&gamma)
+                .add(&commitment.sub(&G1Point::from_scalar(r_z)).mult(&factor));
+        }
+        let vanishing_z = Polynomial::vanishing(&all_points)?
+            .evaluate(z.clone())?
+            .result;
+        let combined = combined.sub(&self.quotient.mult(&vanishing_z));
+
+        // e(F + z W', G2) e(-W', [s]_2) = 1
+        let lhs = combined.add(&self.opening.mult(&z));
+        let rhs = G1Point::from_i128(0).sub(&self.opening);
+        Ok(multi_pairing_is_one(&[
+            (&lhs, &G2Point::from_i128(1)),
+            (&rhs, &setup_artifacts[1].g2),
+        ]))
+    }
+}
+
+/// Returns the distinct points `T` of all the sets of evaluations
+fn union(evaluations: &[MultiPointEvaluation]) -> Vec<Scalar> {
+    let mut points: Vec<Scalar> = vec![];
+    for point in evaluations.iter().flat_map(|e| e.points()) {
+        if !points.contains(&point) {
+            points.push(point);
+        }
+    }
+    points
+}
+
+/// Returns the points of `T` that are not in the set `S_i` of the evaluations
+fn complement(all_points: &[Scalar], evaluation: &MultiPointEvaluation) -> Vec<Scalar> {
+    let points = evaluation.points();
+    all_points
+        .iter()
+        .filter(|point| !points.contains(point))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::ParallelSetupArtifactsGenerator;
+
+    fn scalars(values: &[i128]) -> Vec<Scalar> {
+        values.iter().map(|v| Scalar::from_i128(*v)).collect()
+    }
+
+    #[test]
+    fn test_shplonk_opening() {
+        let artifacts = ParallelSetupArtifactsGenerator::new([8; 32]).generate(8);
+        let polynomials = vec![
+            Polynomial::try_from(vec![1, 2, 3]).unwrap(),
+            Polynomial::try_from(vec![-4, 0, 0, 0, 1]).unwrap(),
+            Polynomial::try_from(vec![5, -1, 0, 2, 0, 0, 1]).unwrap(),
+        ];
+        let commitments = polynomials
+            .iter()
+            .map(|p| p.commit(&artifacts).unwrap())
+            .collect::<Vec<G1Point>>();
+        // Sets of 1, 2 and 3 points, overlapping at 2
+        let evaluations = [scalars(&[2]), scalars(&[2, -1]), scalars(&[0, 2, 9])]
+            .iter()
+            .zip(&polynomials)
+            .map(|(points, p)| MultiPointEvaluation::new(p, points).unwrap())
+            .collect::<Vec<MultiPointEvaluation>>();
+
+        let proof = ShplonkProof::generate(&polynomials, &evaluations, &artifacts).unwrap();
+        assert!(
+            proof
+                .verify(&commitments, &evaluations, &artifacts)
+                .unwrap()
+        );
+
+        // A wrong evaluation is rejected by the verifier and can not be proven
+        let mut wrong = evaluations.clone();
+        wrong[2].evaluations[1].result = wrong[2].evaluations[1].result.add(&Scalar::from_i128(1));
+        assert!(!proof.verify(&commitments, &wrong, &artifacts).unwrap());
+        assert!(ShplonkProof::generate(&polynomials, &wrong, &artifacts).is_err());
+
+        // Commitments in another order are rejected
+        let mut swapped = commitments.clone();
+        swapped.swap(0, 1);
+        assert!(!proof.verify(&swapped, &evaluations, &artifacts).unwrap());
+
+        // A single polynomial at a single point
+        let single =
+            ShplonkProof::generate(&polynomials[..1], &evaluations[..1], &artifacts).unwrap();
+        assert!(
+            single
+                .verify(&commitments[..1], &evaluations[..1], &artifacts)
+                .unwrap()
+        );
+        assert!(
+            single
+                .verify(&commitments, &evaluations, &artifacts)
+                .is_ok_and(|v| !v)
+        );
+        assert!(
+            proof
+                .verify(&commitments[..2], &evaluations, &artifacts)
+                .is_err()
+        );
+
+        // Empty sets and repeated points are rejected
+        let mut empty = evaluations.clone();
+        empty[0].evaluations.clear();
+        assert!(ShplonkProof::generate(&polynomials, &empty, &artifacts).is_err());
+        assert!(proof.verify(&commitments, &empty, &artifacts).is_err());
+        let mut repeated = evaluations.clone();
+        repeated[2].evaluations[0] = repeated[2].evaluations[1].clone();
+        assert!(ShplonkProof::generate(&polynomials, &repeated, &artifacts).is_err());
+        assert!(proof.verify(&commitments, &repeated, &artifacts).is_err());
+    }
+}
@@ -0,0 +1,518 @@
+//! Interactive KZG opening between a prover and a verifier over a stream, e.g. a TCP or a Unix socket.
+//!
+//! The exchange is:
+//! 1. the prover sends the hash of its setup and the commitment to its polynomial,
+//! 2. the verifier checks that the setups match and sends a random challenge point `z`,
+//! 3. the prover answers with the evaluation `y = P(z)` and the proof of the evaluation,
+//! 4. the verifier checks the proof with `Evaluation::verify_proof` and sends the outcome.
+//!
+//! Either side may send an abort message with a reason instead of the expected message.
+//!
+//! Each message is a frame made of a one byte tag, the length of the payload as a big endian `u32` and the payload.
+//! Scalars are encoded in 32 bytes big endian, points are compressed and the setup hash is given as its 32 bytes.
+use std::{
+    fmt::Display,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+};
+#[cfg(unix)]
+use std::{
+    fs,
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+};
+
+use crate::{
+    curves::G1Point,
+    polynomial::{Evaluation, Polynomial},
+    scalar::Scalar,
+    trusted_setup::Setup,
+};
+
+/// Maximum size of the payload of a frame, in bytes
+const MAX_PAYLOAD_SIZE: usize = 4096;
+
+const COMMITMENT_TAG: u8 = 1;
+const CHALLENGE_TAG: u8 = 2;
+const OPENING_TAG: u8 = 3;
+const OUTCOME_TAG: u8 = 4;
+const ABORT_TAG: u8 = 5;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Commitment {
+        setup_hash: [u8; 32],
+        commitment: G1Point,
+    },
+    Challenge {
+        point: Scalar,
+    },
+    Opening {
+        result: Scalar,
+        proof: G1Point,
+    },
+    Outcome {
+        verified: bool,
+    },
+    Abort {
+        reason: String,
+    },
+}
+
+impl Message {
+    fn encode(&self) -> (u8, Vec<u8>) {
+        match self {
+            Message::Commitment {
+                setup_hash,
+                commitment,
+            } => (
+                COMMITMENT_TAG,
+                [&setup_hash[..], &commitment.to_compressed_bytes()].concat(),
+            ),
+            Message::Challenge { point } => (CHALLENGE_TAG, point.to_be_bytes().to_vec()),
+            Message::Opening { result, proof } => (
+                OPENING_TAG,
+                [&result.to_be_bytes()[..], &proof.to_compressed_bytes()].concat(),
+            ),
+            Message::Outcome { verified } => (OUTCOME_TAG, vec![*verified as u8]),
+            Message::Abort { reason } => (ABORT_TAG, reason.as_bytes().to_vec()),
+        }
+    }
+
+    fn decode(tag: u8, payload: &[u8]) -> Result<Self, anyhow::Error> {
+        let expect_length = |length: usize| {
+            if payload.len() != length {
+                return Err(anyhow::anyhow!(
+                    "Invalid payload of message {tag}, expected {length} bytes, got {}",
+                    payload.len()
+                ));
+            }
+            Ok(())
+        };
+        let message = match tag {
+            COMMITMENT_TAG => {
+                expect_length(32 + 48)?;
+                Message::Commitment {
+                    setup_hash: payload[..32].try_into()?,
+                    commitment: G1Point::from_compressed_bytes_in_group(&payload[32..])?,
+                }
+            }
+            CHALLENGE_TAG => {
+                expect_length(32)?;
+                Message::Challenge {
+                    point: decode_scalar(payload)?,
+                }
+            }
+            OPENING_TAG => {
+                expect_length(32 + 48)?;
+                Message::Opening {
+                    result: decode_scalar(&payload[..32])?,
+                    proof: G1Point::from_compressed_bytes_in_group(&payload[32..])?,
+                }
+            }
+            OUTCOME_TAG => {
+                expect_length(1)?;
+                Message::Outcome {
+                    verified: match payload[0] {
+                        0 => false,
+                        1 => true,
+                        other => return Err(anyhow::anyhow!("Invalid outcome {other}")),
+                    },
+                }
+            }
+            ABORT_TAG => Message::Abort {
+                reason: String::from_utf8_lossy(payload).into_owned(),
+            },
+            other => return Err(anyhow::anyhow!("Unknown message tag {other}")),
+        };
+        Ok(message)
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Message::Commitment { .. } => "commitment",
+            Message::Challenge { .. } => "challenge",
+            Message::Opening { .. } => "opening",
+            Message::Outcome { .. } => "outcome",
+            Message::Abort { .. } => "abort",
+        }
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::Commitment {
+                setup_hash,
+                commitment,
+            } => write!(
+                f,
+                "commitment setup_hash={} commitment=0x{}",
+                hex::encode(setup_hash),
+                hex::encode(commitment.to_compressed_bytes())
+            ),
+            Message::Challenge { point } => write!(f, "challenge z={point}"),
+            Message::Opening { result, proof } => write!(
+                f,
+                "opening y={result} proof=0x{}",
+                hex::encode(proof.to_compressed_bytes())
+            ),
+            Message::Outcome { verified } => write!(f, "outcome verified={verified}"),
+            Message::Abort { reason } => write!(f, "abort reason=\"{reason}\""),
+        }
+    }
+}
+
+fn decode_scalar(be_bytes: &[u8]) -> Result<Scalar, anyhow::Error> {
+    Scalar::from_be_bytes_strict(be_bytes.try_into()?)
+}
+
+/// Stream carrying the messages, e.g. a TCP or a Unix socket
+pub trait Stream: Read + Write {}
+
+impl<T: Read + Write> Stream for T {}
+
+/// Address of a socket, either `<host>:<port>` for TCP or `unix:<path>` for a Unix socket on Unix platforms
+#[derive(Debug, Clone)]
+pub enum Endpoint {
+    Tcp(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for Endpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("unix:") {
+            Some("") => Err(anyhow::anyhow!("Missing path of the Unix socket")),
+            #[cfg(unix)]
+            Some(path) => Ok(Endpoint::Unix(PathBuf::from(path))),
+            #[cfg(not(unix))]
+            Some(_) => Err(anyhow::anyhow!(
+                "Unix sockets are not supported on this platform"
+            )),
+            None => Ok(Endpoint::Tcp(s.to_owned())),
+        }
+    }
+}
+
+impl Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Tcp(address) => write!(f, "{address}"),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl Endpoint {
+    /// Connects to the endpoint
+    pub fn connect(&self) -> Result<Box<dyn Stream>, anyhow::Error> {
+        let stream: Box<dyn Stream> = match self {
+            Endpoint::Tcp(address) => Box::new(TcpStream::connect(address)?),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Box::new(UnixStream::connect(path)?),
+        };
+        Ok(stream)
+    }
+
+    /// Listens on the endpoint and accepts a single connection.
+    ///
+    /// A stale Unix socket at the path is removed beforehand, the socket file is removed once the connection is accepted.
+    pub fn accept(&self) -> Result<Box<dyn Stream>, anyhow::Error> {
+        let stream: Box<dyn Stream> = match self {
+            Endpoint::Tcp(address) => Box::new(TcpListener::bind(address)?.accept()?.0),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                if fs::metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+                    fs::remove_file(path)?;
+                }
+                let stream = UnixListener::bind(path)?.accept()?.0;
+                fs::remove_file(path)?;
+                Box::new(stream)
+            }
+        };
+        Ok(stream)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+/// Log of the messages exchanged by one side of the protocol
+#[derive(Debug, Default)]
+pub struct Transcript {
+    pub entries: Vec<(Direction, Message)>,
+}
+
+impl Display for Transcript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (direction, message) in &self.entries {
+            match direction {
+                Direction::Sent => writeln!(f, "> {message}")?,
+                Direction::Received => writeln!(f, "< {message}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Framed channel over a stream, every message sent or received is recorded in the transcript
+pub struct Channel<S: Stream> {
+    stream: S,
+    pub transcript: Transcript,
+}
+
+impl<S: Stream> Channel<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            transcript: Transcript::default(),
+        }
+    }
+
+    pub fn send(&mut self, message: Message) -> Result<(), anyhow::Error> {
+        let (tag, payload) = message.encode();
+        self.stream.write_all(&[tag])?;
+        self.stream
+            .write_all(&(payload.len() as u32).to_be_bytes())?;
+        self.stream.write_all(&payload)?;
+        self.stream.flush()?;
+
+        log::debug!("Sent {message}");
+        self.transcript.entries.push((Direction::Sent, message));
+        Ok(())
+    }
+
+    pub fn receive(&mut self) -> Result<Message, anyhow::Error> {
+        let mut header = [0; 5];
+        self.stream
+            .read_exact(&mut header)
+            .map_err(|e| anyhow::anyhow!("Unable to read the next message: {e}"))?;
+        let length = u32::from_be_bytes(header[1..].try_into()?) as usize;
+        if length > MAX_PAYLOAD_SIZE {
+            return Err(anyhow::anyhow!(
+                "Message of {length} bytes exceeds the limit of {MAX_PAYLOAD_SIZE} bytes"
+            ));
+        }
+        let mut payload = vec![0; length];
+        self.stream.read_exact(&mut payload)?;
+        let message = Message::decode(header[0], &payload)?;
+
+        log::debug!("Received {message}");
+        self.transcript
+            .entries
+            .push((Direction::Received, message.clone()));
+        Ok(message)
+    }
+
+    /// Receives a message and checks that it is of the expected kind, an abort of the other side is turned into an error
+    fn receive_expected(&mut self, kind: &str) -> Result<Message, anyhow::Error> {
+        match self.receive()? {
+            Message::Abort { reason } => Err(anyhow::anyhow!(
+                "The other side aborted the protocol: {reason}"
+            )),
+            message if message.kind() == kind => Ok(message),
+            message => Err(anyhow::anyhow!(
+                "Expected a {kind} message, got a {} message",
+                message.kind()
+            )),
+        }
+    }
+
+    /// Sends an abort message and returns the matching error
+    fn abort(&mut self, reason: String) -> anyhow::Error {
+        if let Err(e) = self.send(Message::Abort {
+            reason: reason.clone(),
+        }) {
+            log::warn!("Unable to send the abort message: {e}");
+        }
+        anyhow::anyhow!(reason)
+    }
+}
+
+/// Runs the prover side of the protocol and returns the outcome sent by the verifier
+///
+/// * `channel` - Channel to the verifier
+/// * `polynomial` - Polynomial to commit to and to open at the challenge point
+/// * `setup` - Setup shared with the verifier
+pub fn run_prover<S: Stream>(
+    channel: &mut Channel<S>,
+    polynomial: &Polynomial,
+    setup: &Setup,
+) -> Result<bool, anyhow::Error> {
+    channel.send(Message::Commitment {
+        setup_hash: setup_hash_bytes(setup)?,
+        commitment: polynomial.commit(&setup.artifacts)?,
+    })?;
+
+    let Message::Challenge { point } = channel.receive_expected("challenge")? else {
+        unreachable!("the kind of the message has been checked");
+    };
+    let evaluation = polynomial.evaluate(point)?;
+    let proof = evaluation.generate_proof(polynomial, &setup.artifacts)?;
+    channel.send(Message::Opening {
+        result: evaluation.result,
+        proof,
+    })?;
+
+    let Message::Outcome { verified } = channel.receive_expected("outcome")? else {
+        unreachable!("the kind of the message has been checked");
+    };
+    Ok(verified)
+}
+
+/// Runs the verifier side of the protocol and returns whether the opening of the prover is correct
+///
+/// * `channel` - Channel to the prover
+/// * `setup` - Setup shared with the prover
+/// * `challenge` - Point at which the prover must open its commitment, it must be unpredictable by the prover
+pub fn run_verifier<S: Stream>(
+    channel: &mut Channel<S>,
+    setup: &Setup,
+    challenge: Scalar,
+) -> Result<bool, anyhow::Error> {
+    let Message::Commitment {
+        setup_hash,
+        commitment,
+    } = channel.receive_expected("commitment")?
+    else {
+        unreachable!("the kind of the message has been checked");
+    };
+    if setup_hash != setup_hash_bytes(setup)? {
+        return Err(channel.abort(format!(
+            "The commitment has been made with the setup {} while the verifier uses the setup {}",
+            hex::encode(setup_hash),
+            setup.hash()
+        )));
+    }
+
+    channel.send(Message::Challenge {
+        point: challenge.clone(),
+    })?;
+
+    let Message::Opening { result, proof } = channel.receive_expected("opening")? else {
+        unreachable!("the kind of the message has been checked");
+    };
+    let evaluation = Evaluation {
+        point: challenge,
+        result,
+    };
+    let verified = evaluation.verify_proof(&proof, &commitment, &setup.artifacts)?;
+    channel.send(Message::Outcome { verified })?;
+    Ok(verified)
+}
+
+fn setup_hash_bytes(setup: &Setup) -> Result<[u8; 32], anyhow::Error> {
+    hex::decode(setup.hash())?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("The setup hash must be 32 bytes long"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator};
+    use std::thread;
+
+    /// Returns both ends of a local TCP connection
+    fn stream_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        (client, listener.accept().unwrap().0)
+    }
+
+    fn setup(secret: u8) -> Setup {
+        Setup::new(
+            ParallelSetupArtifactsGenerator::new([secret; 32]).generate(4),
+            GenerationMode::RandomSecret,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prover_and_verifier() {
+        let (prover_stream, verifier_stream) = stream_pair();
+        let prover = thread::spawn(move || {
+            let mut channel = Channel::new(prover_stream);
+            let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+            let verified = run_prover(&mut channel, &polynomial, &setup(3)).unwrap();
+            (verified, channel.transcript)
+        });
+
+        let mut channel = Channel::new(verifier_stream);
+        let verified = run_verifier(&mut channel, &setup(3), Scalar::from_i128(5)).unwrap();
+        let (prover_verified, prover_transcript) = prover.join().unwrap();
+
+        assert!(verified);
+        assert!(prover_verified);
+        assert_eq!(prover_transcript.entries.len(), 4);
+        let lines: Vec<String> = channel
+            .transcript
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect();
+        assert!(lines[0].starts_with("< commitment"));
+        assert_eq!(lines[1], "> challenge z=5");
+        assert!(lines[2].starts_with("< opening y=86 proof=0x"));
+        assert_eq!(lines[3], "> outcome verified=true");
+    }
+
+    #[test]
+    fn test_verifier_rejects_wrong_opening_and_other_setup() {
+        // The prover answers with a wrong evaluation
+        let (prover_stream, verifier_stream) = stream_pair();
+        let prover = thread::spawn(move || {
+            let setup = setup(3);
+            let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+            let mut channel = Channel::new(prover_stream);
+            channel
+                .send(Message::Commitment {
+                    setup_hash: setup_hash_bytes(&setup).unwrap(),
+                    commitment: polynomial.commit(&setup.artifacts).unwrap(),
+                })
+                .unwrap();
+            let Message::Challenge { point } = channel.receive().unwrap() else {
+                panic!("expected a challenge");
+            };
+            let evaluation = polynomial.evaluate(point).unwrap();
+            let proof = evaluation
+                .generate_proof(&polynomial, &setup.artifacts)
+                .unwrap();
+            channel
+                .send(Message::Opening {
+                    result: evaluation.result.add(&Scalar::from_i128(1)),
+                    proof,
+                })
+                .unwrap();
+            channel.receive().unwrap()
+        });
+        let mut channel = Channel::new(verifier_stream);
+        assert!(!run_verifier(&mut channel, &setup(3), Scalar::from_i128(5)).unwrap());
+        assert!(matches!(
+            prover.join().unwrap(),
+            Message::Outcome { verified: false }
+        ));
+
+        // The prover uses another setup
+        let (prover_stream, verifier_stream) = stream_pair();
+        let prover = thread::spawn(move || {
+            let polynomial = Polynomial::try_from(vec![1, 2, 3]).unwrap();
+            run_prover(&mut Channel::new(prover_stream), &polynomial, &setup(4))
+        });
+        let mut channel = Channel::new(verifier_stream);
+        assert!(run_verifier(&mut channel, &setup(3), Scalar::from_i128(5)).is_err());
+        let error = prover.join().unwrap().unwrap_err().to_string();
+        assert!(error.starts_with("The other side aborted the protocol"));
+    }
+}
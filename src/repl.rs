@@ -0,0 +1,321 @@
+//! Interactive session to explore polynomials, commitments and proofs.
+//!
+//! The session keeps a setup and named polynomials in memory, the supported commands are:
+//! - `p = 3x^2 + 2x + 1`: defines a polynomial, the right side can also be a name or a division `p / (x - 5)`,
+//! - `p`, `p / (x - 5)`: displays a polynomial or a division with its quotient and remainder,
+//! - `commit p`: commits to a polynomial,
+//! - `open p at 5`: evaluates a polynomial and generates the proof of the evaluation,
+//! - `verify`: verifies the last opening against the commitment of its polynomial,
+//! - `list`, `help`, `exit`.
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use crate::{
+    curves::G1Point,
+    polynomial::{Evaluation, Polynomial},
+    scalar::Scalar,
+    trusted_setup::SetupArtifact,
+};
+
+const HELP: &str = "Commands:
+  p = 3x^2 + 2x + 1   define a polynomial, the right side can also be a name or `p / (x - 5)`
+  q = (x - 1)(x - 2)  expressions support `+`, `-`, `*`, `^` and parentheses
+  p                   display a polynomial
+  p / (x - 5)         divide a polynomial and display the quotient and the remainder
+  commit p            commit to a polynomial
+  open p at 5         evaluate a polynomial and prove the evaluation
+  verify              verify the last opening
+  list                list the polynomials
+  help                show this message
+  exit                leave the session";
+
+/// Opening of a named polynomial at a point
+struct ReplOpening {
+    name: String,
+    evaluation: Evaluation,
+    proof: G1Point,
+}
+
+pub struct Repl {
+    setup_artifacts: Vec<SetupArtifact>,
+    polynomials: BTreeMap<String, Polynomial>,
+    commitments: BTreeMap<String, G1Point>,
+    last_opening: Option<ReplOpening>,
+}
+
+impl Repl {
+    /// Creates a session without any polynomial
+    ///
+    /// * `setup_artifacts` - Setup artifacts used for commitments and proofs
+    pub fn new(setup_artifacts: Vec<SetupArtifact>) -> Self {
+        Self {
+            setup_artifacts,
+            polynomials: BTreeMap::new(),
+            commitments: BTreeMap::new(),
+            last_opening: None,
+        }
+    }
+
+    /// Runs the session until the end of the input or the `exit` command, errors of the commands are printed and the session goes on
+    ///
+    /// * `reader` - Source of the commands
+    /// * `writer` - Destination of the prompts and results
+    pub fn run(
+        &mut self,
+        reader: impl BufRead,
+        mut writer: impl Write,
+    ) -> Result<(), anyhow::Error> {
+        write!(writer, "> ")?;
+        writer.flush()?;
+        for line in reader.lines() {
+            let line = line?;
+            let command = line.trim();
+            if command == "exit" || command == "quit" {
+                break;
+            }
+            match self.execute(command) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => writeln!(writer, "{output}")?,
+                Err(e) => writeln!(writer, "error: {e}")?,
+            }
+            write!(writer, "> ")?;
+            writer.flush()?;
+        }
+        writeln!(writer)?;
+        Ok(())
+    }
+
+    /// Executes a command and returns what must be printed
+    ///
+    /// * `command` - Command of the session
+    pub fn execute(&mut self, command: &str) -> Result<String, anyhow::Error> {
+        if command.is_empty() {
+            return Ok(String::new());
+        }
+        if command == "help" {
+            return Ok(HELP.to_owned());
+        }
+        if command == "list" {
+            return Ok(self
+                .polynomials
+                .iter()
+                .map(|(name, polynomial)| format!("{name}(x) = {polynomial}"))
+                .collect::<Vec<String>>()
+                .join("\n"));
+        }
+        if command == "verify" {
+            return self.verify();
+        }
+        if let Some(name) = command.strip_prefix("commit ") {
+            return self.commit(name.trim());
+        }
+        if let Some(rest) = command.strip_prefix("open ") {
+            let (name, point) = rest
+                .split_once(" at ")
+                .ok_or_else(|| anyhow::anyhow!("Expected `open <name> at <point>`"))?;
+            return self.open(name.trim(), Scalar::from_str(point.trim())?);
+        }
+        if let Some((name, expression)) = command.split_once('=') {
+            let name = name.trim();
+            validate_name(name)?;
+            let (polynomial, mut output) = self.evaluate_expression(expression.trim())?;
+            output.push(format!("{name}(x) = {polynomial}"));
+            self.commitments.remove(name);
+            self.polynomials.insert(name.to_owned(), polynomial);
+            return Ok(output.join("\n"));
+        }
+
+        let (polynomial, output) = self.evaluate_expression(command)?;
+        if output.is_empty() {
+            return Ok(polynomial.to_string());
+        }
+        Ok(output.join("\n"))
+    }
+
+    /// Evaluates the right side of a definition, returns the polynomial and the intermediate values to print
+    fn evaluate_expression(
+        &self,
+        expression: &str,
+    ) -> Result<(Polynomial, Vec<String>), anyhow::Error> {
+        if let Some((dividend, divisor)) = expression.split_once('/') {
+            let dividend = self.resolve(dividend.trim())?;
+            let divisor = self.resolve(divisor.trim())?;
+            let (quotient, remainder) = dividend.divide(&divisor)?;
+            let output = vec![
+                format!("quotient: {quotient}"),
+                format!("remainder: {remainder}"),
+            ];
+            return Ok((quotient, output));
+        }
+        Ok((self.resolve(expression)?, vec![]))
+    }
+
+    /// Resolves a name of the session or parses a polynomial
+    fn resolve(&self, expression: &str) -> Result<Polynomial, anyhow::Error> {
+        match self.polynomials.get(expression) {
+            Some(polynomial) => Ok(polynomial.clone()),
+            None => Ok(Polynomial::parse_with_max_degree(
+                expression,
+                self.setup_artifacts.len().saturating_sub(1) as u32,
+            )?),
+        }
+    }
+
+    fn polynomial(&self, name: &str) -> Result<&Polynomial, anyhow::Error> {
+        self.polynomials
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Unknown polynomial \"{name}\", define it first"))
+    }
+
+    fn commit(&mut self, name: &str) -> Result<String, anyhow::Error> {
+        let commitment = self.polynomial(name)?.commit(&self.setup_artifacts)?;
+        let output = format!(
+            "commitment of {name}: 0x{}",
+            hex::encode(commitment.to_compressed_bytes())
+        );
+        self.commitments.insert(name.to_owned(), commitment);
+        Ok(output)
+    }
+
+    fn open(&mut self, name: &str, point: Scalar) -> Result<String, anyhow::Error> {
+        let polynomial = self.polynomial(name)?;
+        let evaluation = polynomial.evaluate(point)?;
+        let quotient = evaluation.quotient(polynomial)?;
+        let proof = evaluation.generate_proof(polynomial, &self.setup_artifacts)?;
+        let output = [
+            format!("{name}({}) = {}", evaluation.point, evaluation.result),
+            format!(
+                "quotient: ({name}(x) - {}) / (x - {}) = {quotient}",
+                evaluation.result, evaluation.point
+            ),
+            format!("proof: 0x{}", hex::encode(proof.to_compressed_bytes())),
+        ]
+        .join("\n");
+        self.last_opening = Some(ReplOpening {
+            name: name.to_owned(),
+            evaluation,
+            proof,
+        });
+        Ok(output)
+    }
+
+    fn verify(&self) -> Result<String, anyhow::Error> {
+        let opening = self
+            .last_opening
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Nothing to verify, open a polynomial first"))?;
+        let commitment = self.commitments.get(&opening.name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "The polynomial \"{}\" has not been committed, commit it first",
+                opening.name
+            )
+        })?;
+        let verified =
+            opening
+                .evaluation
+                .verify_proof(&opening.proof, commitment, &self.setup_artifacts)?;
+        Ok(format!(
+            "{}({}) = {}: {}",
+            opening.name,
+            opening.evaluation.point,
+            opening.evaluation.result,
+            if verified {
+                "verified"
+            } else {
+                "invalid proof"
+            }
+        ))
+    }
+}
+
+/// Names start with a letter other than `x` and are made of ASCII alphanumeric characters and `_`
+fn validate_name(name: &str) -> Result<(), anyhow::Error> {
+    let is_valid = name != "x"
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid {
+        return Err(anyhow::anyhow!(
+            "Invalid name \"{name}\", names start with a letter, are made of letters, digits and '_' and cannot be \"x\""
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trusted_setup::ParallelSetupArtifactsGenerator;
+
+    #[test]
+    fn test_repl_session() {
+        let mut repl = Repl::new(ParallelSetupArtifactsGenerator::new([5; 32]).generate(4));
+
+        assert_eq!(
+            repl.execute("p = 3x^2 + 2x + 1").unwrap(),
+            "p(x) = 3x^2 + 2x + 1"
+        );
+        assert!(repl.execute("verify").is_err());
+        assert!(
+            repl.execute("commit p")
+                .unwrap()
+                .starts_with("commitment of p: 0x")
+        );
+        let opening = repl.execute("open p at 5").unwrap();
+        assert!(opening.starts_with("p(5) = 86\n"));
+        assert_eq!(repl.execute("verify").unwrap(), "p(5) = 86: verified");
+
+        assert_eq!(
+            repl.execute("p / (x - 5)").unwrap(),
+            "quotient: 3x + 17\nremainder: 86"
+        );
+        assert_eq!(
+            repl.execute("q = x^2 - 1").unwrap(),
+            "q(x) = 1x^2 + 52435875175126190479447740508185965837690552500527637822603658699938581184512"
+        );
+        assert_eq!(
+            repl.execute("r = q / (x + 1)").unwrap(),
+            "quotient: 1x + 52435875175126190479447740508185965837690552500527637822603658699938581184512\nremainder: 0\nr(x) = 1x + 52435875175126190479447740508185965837690552500527637822603658699938581184512"
+        );
+
+        // The divisor is parsed as a whole expression, including products of parentheses
+        repl.execute("s = (x + 1)(x + 2)(x + 3)").unwrap();
+        assert_eq!(
+            repl.execute("s / (x + 1)(x + 2)").unwrap(),
+            "quotient: 1x + 3\nremainder: 0"
+        );
+
+        assert_eq!(
+            repl.execute("(x - 1)(x + 1) - x^2").unwrap(),
+            "52435875175126190479447740508185965837690552500527637822603658699938581184512"
+        );
+        assert_eq!(
+            repl.execute("p = 3x^").unwrap_err().to_string(),
+            "Expected an exponent at column 4"
+        );
+
+        // Redefining a polynomial drops its commitment
+        repl.execute("p = x").unwrap();
+        assert!(repl.execute("verify").is_err());
+        assert!(repl.execute("open z at 1").is_err());
+        assert!(repl.execute("x = 1").is_err());
+    }
+
+    #[test]
+    fn test_repl_run() {
+        let mut repl = Repl::new(ParallelSetupArtifactsGenerator::new([5; 32]).generate(3));
+        let input = "p = x + 1\ncommit p\nopen p at 2\nverify\nunknown\nexit\nlist\n";
+        let mut output = vec![];
+        repl.run(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("p(2) = 3: verified"));
+        // Nothing is executed after `exit`: the output ends with the prompt following the error of `unknown`
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert!(lines[lines.len() - 2].starts_with("> error: "));
+        assert_eq!(lines[lines.len() - 1], "> ");
+        assert!(output.ends_with("> \n"));
+        assert_eq!(output.matches("p(x) = 1x + 1").count(), 1);
+    }
+}
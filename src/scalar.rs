@@ -2,7 +2,7 @@ use serde::{
     Deserialize, Serialize,
     de::{self, Visitor},
 };
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Scalar(blst::blst_fr);
@@ -72,6 +72,39 @@ impl Scalar {
         Self(fr)
     }
 
+    /// Creates a scalar from big endian bytes and rejects the values that are not lower than r
+    ///
+    /// * `b` - Big endian byte array of length 32
+    pub fn from_be_bytes_strict(b: [u8; 32]) -> Result<Self, anyhow::Error> {
+        let mut scalar = blst::blst_scalar::default();
+        let mut fr = blst::blst_fr::default();
+        unsafe {
+            blst::blst_scalar_from_bendian(&mut scalar, b.as_ptr());
+            if !blst::blst_scalar_fr_check(&scalar) {
+                return Err(anyhow::anyhow!(
+                    "Invalid scalar 0x{}, the value is not lower than the field modulus 0x{R_AS_HEX}",
+                    hex::encode(b)
+                ));
+            }
+            blst::blst_fr_from_scalar(&mut fr, &scalar);
+        }
+        Ok(Self(fr))
+    }
+
+    /// Creates a scalar from 64 big endian bytes reduced modulo r, e.g. to derive uniform scalars from random bytes
+    ///
+    /// * `b` - Big endian byte array of length 64
+    pub fn from_be_bytes_wide(b: &[u8; 64]) -> Self {
+        let mut scalar = blst::blst_scalar::default();
+        let mut fr = blst::blst_fr::default();
+        unsafe {
+            // The returned flag only tells whether the reduced value is zero
+            blst::blst_scalar_from_be_bytes(&mut scalar, b.as_ptr(), b.len());
+            blst::blst_fr_from_scalar(&mut fr, &scalar);
+        }
+        Self(fr)
+    }
+
     /// Creates a scalar from a i128
     ///
     /// * `a` - i128 value
@@ -155,12 +188,12 @@ impl Scalar {
         //  Target factor = 28
         //  Power of two decomposition: [self^2, self^4, self^8, self^16]
         //  self^28 = self^16 * self^8 * self^4
-        let (mut self_powered_by_target_factor, mut self_power_tracker) = if target_factor % 2 == 0
-        {
-            (Scalar::from_i128(1), 0)
-        } else {
-            (self.clone(), 1)
-        };
+        let (mut self_powered_by_target_factor, mut self_power_tracker) =
+            if target_factor.is_multiple_of(2) {
+                (Scalar::from_i128(1), 0)
+            } else {
+                (self.clone(), 1)
+            };
         let mut available_power = 2usize.pow(powered_scalars.len() as u32);
         while self_power_tracker != target_factor {
             if self_power_tracker + available_power <= target_factor {
@@ -217,6 +250,60 @@ impl Scalar {
         Scalar(out)
     }
 
+    /// Returns a new scalar obtained by raising self to a 256-bit exponent
+    ///
+    /// - `exponent` - Low endian bytes of the exponent
+    pub fn pow_le_bytes(&self, exponent: &[u8; 32]) -> Self {
+        let mut out = Scalar::from_i128(1);
+        for byte in exponent.iter().rev() {
+            for bit in (0..8).rev() {
+                out = out.mul(&out);
+                if (byte >> bit) & 1 == 1 {
+                    out = out.mul(self);
+                }
+            }
+        }
+        out
+    }
+
+    /// Returns the multiplicative inverse of self, fails if self is zero
+    pub fn inv(&self) -> Result<Self, anyhow::Error> {
+        if self.is_zero() {
+            return Err(anyhow::anyhow!(
+                "Zero does not have a multiplicative inverse"
+            ));
+        }
+        let mut out = blst::blst_fr::default();
+        unsafe {
+            blst::blst_fr_eucl_inverse(&mut out, &self.0);
+        }
+        Ok(Scalar(out))
+    }
+
+    /// Parses a scalar and rejects the inputs that are not the canonical representation of an element of the field.
+    ///
+    /// Accepted inputs are the decimal or `0x` prefixed hexadecimal values in `[0, r)`, negative values and values larger than or equal to `r` are rejected.
+    ///
+    /// * `s` - Decimal or hexadecimal value
+    pub fn from_str_strict(s: &str) -> Result<Self, anyhow::Error> {
+        let parsed = ParsedInteger::parse(s)?;
+        if parsed.is_negative {
+            return Err(anyhow::anyhow!(
+                "Invalid scalar \"{s}\", negative values are not canonical"
+            ));
+        }
+        let r_be_bytes = hex::decode(R_AS_HEX)?;
+        let magnitude_be_bytes = parsed.magnitude_be_bytes();
+        let is_lower_than_r = magnitude_be_bytes.len() < r_be_bytes.len()
+            || (magnitude_be_bytes.len() == r_be_bytes.len() && magnitude_be_bytes < r_be_bytes);
+        if !is_lower_than_r {
+            return Err(anyhow::anyhow!(
+                "Invalid scalar \"{s}\", the value is not lower than the field modulus 0x{R_AS_HEX}"
+            ));
+        }
+        Ok(parsed.reduce())
+    }
+
     /// Returns true if self is the representation of zero, false otherwise
     pub fn is_zero(&self) -> bool {
         self.0 == blst::blst_fr::default()
@@ -274,6 +361,119 @@ impl<'de> Deserialize<'de> for Scalar {
     }
 }
 
+impl FromStr for Scalar {
+    type Err = anyhow::Error;
+
+    /// Parses a decimal value of up to 155 digits (about 515 bits) or a `0x` prefixed hexadecimal value of up to 128 digits (512 bits), optionally negative, the value is reduced modulo r.
+    ///
+    /// Use [`Scalar::from_str_strict`] to reject the values that are not canonical.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ParsedInteger::parse(s)?.reduce())
+    }
+}
+
+/// Maximum number of significant decimal digits of a parsed scalar, i.e. about 515 bits, enough for the 512-bit values
+/// reduced without noticeable bias into uniform scalars
+const MAX_DECIMAL_DIGITS: usize = 155;
+/// Maximum number of significant hexadecimal digits of a parsed scalar, i.e. 512 bits
+const MAX_HEXADECIMAL_DIGITS: usize = 128;
+
+/// Integer parsed from a decimal or hexadecimal string, of up to 155 decimal or 128 hexadecimal significant digits
+struct ParsedInteger {
+    is_negative: bool,
+    /// Magnitude of the integer in low endian bytes
+    magnitude_le_bytes: Vec<u8>,
+}
+
+impl ParsedInteger {
+    fn parse(s: &str) -> Result<Self, anyhow::Error> {
+        let trimmed = s.trim();
+        // Positions are counted in characters of the original input, leading whitespace included
+        let mut offset = s.chars().count() - s.trim_start().chars().count();
+        let (is_negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(unsigned) => {
+                offset += 1;
+                (true, unsigned)
+            }
+            None => (false, trimmed),
+        };
+        let (radix, digits, max_digits) = match unsigned
+            .strip_prefix("0x")
+            .or_else(|| unsigned.strip_prefix("0X"))
+        {
+            Some(digits) => {
+                offset += 2;
+                (16, digits, MAX_HEXADECIMAL_DIGITS)
+            }
+            None => (10, unsigned, MAX_DECIMAL_DIGITS),
+        };
+        if digits.is_empty() {
+            return Err(anyhow::anyhow!("Invalid scalar \"{s}\", no digits found"));
+        }
+
+        let digits = digits
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                c.to_digit(radix).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid scalar \"{s}\", unexpected character '{c}' at position {}",
+                        offset + i
+                    )
+                })
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+        // The accumulation is quadratic in the number of digits, which is bounded first
+        let significant_digits = &digits[digits.iter().take_while(|d| **d == 0).count()..];
+        if significant_digits.len() > max_digits {
+            return Err(anyhow::anyhow!(
+                "Invalid scalar \"{s}\", expected at most {max_digits} significant digits, got {}",
+                significant_digits.len()
+            ));
+        }
+
+        // Each digit is accumulated as `magnitude = magnitude * radix + digit`, carried byte per byte
+        let mut magnitude_le_bytes: Vec<u8> = vec![];
+        for digit in significant_digits {
+            let mut carry = *digit;
+            for byte in magnitude_le_bytes.iter_mut() {
+                let value = (*byte as u32) * radix + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            while carry > 0 {
+                magnitude_le_bytes.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+
+        Ok(Self {
+            is_negative,
+            magnitude_le_bytes,
+        })
+    }
+
+    /// Big endian bytes of the magnitude, without leading zeros
+    fn magnitude_be_bytes(&self) -> Vec<u8> {
+        self.magnitude_le_bytes
+            .iter()
+            .rev()
+            .skip_while(|b| **b == 0)
+            .copied()
+            .collect()
+    }
+
+    /// Reduces the integer modulo r
+    fn reduce(&self) -> Scalar {
+        let base = Scalar::from_i128(256);
+        let mut out = Scalar::default();
+        for byte in self.magnitude_le_bytes.iter().rev() {
+            out = out.mul(&base).add(&Scalar::from_i128(*byte as i128));
+        }
+        if self.is_negative { out.neg() } else { out }
+    }
+}
+
 impl Display for Scalar {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match le_bytes_to_base_10_string(&self.to_le_bytes()) {
@@ -335,6 +535,10 @@ fn le_bytes_to_base_10_string(le_bytes: &[u8]) -> Result<String, anyhow::Error>
         digits.push((next_digit as u8) + b'0')
     }
 
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+
     digits.reverse();
 
     String::from_utf8(digits).map_err(|e| e.into())
@@ -398,6 +602,110 @@ mod tests {
         let from_big_uint = format!("{}", BigUint::from_bytes_le(&a));
         let from_scalar = format!("{}", Scalar::from_le_bytes(a));
         assert_eq!(from_big_uint, from_scalar);
+        assert_eq!(Scalar::from_i128(0).to_string(), "0");
+    }
+
+    #[test]
+    fn test_inv() {
+        let a: i128 = Faker.fake();
+        let a = Scalar::from_i128(a);
+        if a.is_zero() {
+            assert!(a.inv().is_err());
+            return;
+        }
+        assert_eq!(a.mul(&a.inv().unwrap()), Scalar::from_i128(1));
+        assert!(Scalar::from_i128(0).inv().is_err());
+    }
+
+    #[test]
+    fn test_scalar_from_str() {
+        let a: i128 = Faker.fake();
+        assert_eq!(
+            Scalar::from_str(&a.to_string()).unwrap(),
+            Scalar::from_i128(a)
+        );
+        assert_eq!(
+            Scalar::from_str(&format!("0x{:x}", a.unsigned_abs())).unwrap(),
+            Scalar::from_i128(a.unsigned_abs() as i128)
+        );
+
+        let r = BigUint::from_bytes_be(&hex::decode(R_AS_HEX).unwrap());
+        let b: [u8; 32] = Faker.fake();
+        let b = BigUint::from_bytes_le(&b);
+        let reduced = Scalar::from_str(&(&r * 3u32 + &b).to_string()).unwrap();
+        assert_eq!(reduced.to_string(), (&b % &r).to_string());
+
+        assert_eq!(Scalar::from_str("-0x1").unwrap(), Scalar::from_i128(-1));
+        assert!(Scalar::from_str("").is_err());
+        assert!(Scalar::from_str("0x").is_err());
+        assert!(Scalar::from_str("12a").is_err());
+
+        // Positions are counted in characters of the untrimmed input
+        assert_eq!(
+            Scalar::from_str("  -0xé1").unwrap_err().to_string(),
+            "Invalid scalar \"  -0xé1\", unexpected character 'é' at position 5"
+        );
+        assert_eq!(
+            Scalar::from_str(" 1é2 ").unwrap_err().to_string(),
+            "Invalid scalar \" 1é2 \", unexpected character 'é' at position 2"
+        );
+
+        // Leading zeros do not count towards the maximum number of digits
+        let zeros = "0".repeat(1000);
+        assert_eq!(
+            Scalar::from_str(&format!("{zeros}12")).unwrap(),
+            Scalar::from_i128(12)
+        );
+        assert!(Scalar::from_str(&format!("0x{}", "f".repeat(128))).is_ok());
+        assert!(Scalar::from_str(&format!("0x{}", "f".repeat(129))).is_err());
+        assert!(Scalar::from_str(&"9".repeat(155)).is_ok());
+        assert!(Scalar::from_str(&"9".repeat(100_000)).is_err());
+    }
+
+    #[test]
+    fn test_scalar_from_be_bytes_wide() {
+        let r = BigUint::from_bytes_be(&hex::decode(R_AS_HEX).unwrap());
+        for bytes in [[0xffu8; 64], core::array::from_fn(|i| i as u8)] {
+            assert_eq!(
+                Scalar::from_be_bytes_wide(&bytes).to_string(),
+                (BigUint::from_bytes_be(&bytes) % &r).to_string()
+            );
+        }
+        assert!(Scalar::from_be_bytes_wide(&[0; 64]).is_zero());
+    }
+
+    #[test]
+    fn test_scalar_from_be_bytes_strict() {
+        let r_be_bytes: [u8; 32] = hex::decode(R_AS_HEX).unwrap().try_into().unwrap();
+        let mut r_minus_one = r_be_bytes;
+        r_minus_one[31] -= 1;
+        assert_eq!(
+            Scalar::from_be_bytes_strict(r_minus_one).unwrap(),
+            Scalar::from_i128(-1)
+        );
+        assert!(Scalar::from_be_bytes_strict([0; 32]).unwrap().is_zero());
+        assert!(Scalar::from_be_bytes_strict(r_be_bytes).is_err());
+        assert!(Scalar::from_be_bytes_strict([0xff; 32]).is_err());
+    }
+
+    #[test]
+    fn test_scalar_from_str_strict() {
+        let r_minus_one = format!("0x{}", &R_AS_HEX[..63]) + "0";
+        assert_eq!(
+            Scalar::from_str_strict(&r_minus_one).unwrap(),
+            Scalar::from_i128(-1)
+        );
+        assert_eq!(
+            Scalar::from_str_strict("0x000001").unwrap(),
+            Scalar::from_i128(1)
+        );
+        assert!(Scalar::from_str_strict(&format!("0x{R_AS_HEX}")).is_err());
+        assert!(Scalar::from_str_strict("-1").is_err());
+        assert!(
+            Scalar::from_str(&format!("0x{R_AS_HEX}"))
+                .unwrap()
+                .is_zero()
+        );
     }
 
     #[test]
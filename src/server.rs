@@ -0,0 +1,409 @@
+//! HTTP service exposing the KZG operations with a setup loaded once.
+//!
+//! Requests and responses are JSON objects, points are compressed and given in hexadecimal and scalars are given as decimal or `0x` prefixed hexadecimal strings:
+//! - `GET /health`: `{"status": "ok"}`,
+//! - `GET /setup/info`: metadata of the setup,
+//! - `POST /commit`: `{"polynomial": "3x^2 + 2x + 1"}` or `{"coefficients": ["1", "2", "3"]}`, returns the commitment,
+//! - `POST /prove`: the polynomial along with `"x"`, returns the commitment, the evaluation `"y"` and the proof,
+//! - `POST /verify`: `{"commitment", "x", "y", "proof"}`, the response of `/prove` can be sent as is,
+//! - `POST /verify-batch`: `{"openings": [...]}` of openings as for `/verify`, returns the result of each of them.
+//!
+//! Failures are answered with the matching status code and a body `{"error": "...", "message": "..."}`.
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{io::Read, net::SocketAddr, str::FromStr};
+
+use crate::{
+    curves::G1Point,
+    polynomial::{Evaluation, Polynomial},
+    scalar::Scalar,
+    trusted_setup::{Setup, SetupMetadata},
+};
+
+/// Default maximum size of a request body, in bytes
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1 << 20;
+
+/// Maximum number of openings verified by a single `/verify-batch` request
+const MAX_BATCH_SIZE: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct ServerOptions {
+    /// Maximum size of a request body, in bytes, larger requests are rejected
+    pub max_body_size: usize,
+    /// Whether the non-canonical scalars must be rejected instead of being reduced modulo r
+    pub strict: bool,
+}
+
+impl Default for ServerOptions {
+    fn default() -> Self {
+        Self {
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            strict: false,
+        }
+    }
+}
+
+/// Error answered to a request
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: u16,
+    pub kind: &'static str,
+    pub message: String,
+}
+
+impl ApiError {
+    fn invalid_input(message: impl Into<String>) -> Self {
+        Self {
+            status: 400,
+            kind: "invalid_input",
+            message: message.into(),
+        }
+    }
+
+    fn internal(error: anyhow::Error) -> Self {
+        Self {
+            status: 500,
+            kind: "internal",
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    error: &'a str,
+    message: &'a str,
+}
+
+/// Polynomial of a request, either as an expression or as its coefficients in ascending degree
+#[derive(Deserialize)]
+struct PolynomialRequest {
+    polynomial: Option<String>,
+    coefficients: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ProveRequest {
+    #[serde(flatten)]
+    polynomial: PolynomialRequest,
+    x: String,
+}
+
+#[derive(Deserialize)]
+struct VerifyRequest {
+    commitment: String,
+    x: String,
+    y: String,
+    proof: String,
+}
+
+#[derive(Deserialize)]
+struct VerifyBatchRequest {
+    openings: Vec<VerifyRequest>,
+}
+
+#[derive(Serialize)]
+struct SetupInfoResponse<'a> {
+    setup_hash: &'a str,
+    #[serde(flatten)]
+    metadata: &'a SetupMetadata,
+}
+
+#[derive(Serialize)]
+struct CommitResponse {
+    polynomial: String,
+    degree: u32,
+    commitment: String,
+    setup_hash: String,
+}
+
+#[derive(Serialize)]
+struct ProveResponse {
+    polynomial: String,
+    commitment: String,
+    x: String,
+    y: String,
+    proof: String,
+    setup_hash: String,
+}
+
+#[derive(Serialize)]
+struct VerifyResponse {
+    verified: bool,
+}
+
+#[derive(Serialize)]
+struct VerifyBatchResponse {
+    verified: bool,
+    results: Vec<bool>,
+}
+
+pub struct Server {
+    http: tiny_http::Server,
+    setup: Setup,
+    options: ServerOptions,
+}
+
+impl Server {
+    /// Binds the service to an address, the setup must have been validated beforehand
+    ///
+    /// * `address` - Address to listen on, e.g. `127.0.0.1:3000`, the port `0` picks a free port
+    /// * `setup` - Setup used by every request
+    /// * `options` - Limits and parsing options of the requests
+    pub fn bind(
+        address: &str,
+        setup: Setup,
+        options: ServerOptions,
+    ) -> Result<Self, anyhow::Error> {
+        let http = tiny_http::Server::http(address)
+            .map_err(|e| anyhow::anyhow!("Unable to listen on \"{address}\": {e}"))?;
+        Ok(Self {
+            http,
+            setup,
+            options,
+        })
+    }
+
+    /// Returns the address the service listens on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    /// Answers the requests one after the other, until the listener fails
+    pub fn run(&self) -> Result<(), anyhow::Error> {
+        for mut request in self.http.incoming_requests() {
+            let method = request.method().as_str().to_owned();
+            let url = request.url().to_owned();
+
+            let result = self
+                .read_body(&mut request)
+                .and_then(|body| self.handle(&method, &url, &body));
+            let (status, body) = match result {
+                Ok(body) => (200, body),
+                Err(e) => {
+                    log::debug!("Request {method} {url} failed: {}", e.message);
+                    let body = serde_json::to_string(&ErrorBody {
+                        error: e.kind,
+                        message: &e.message,
+                    })?;
+                    (e.status, body)
+                }
+            };
+            log::info!("{method} {url} {status}");
+
+            let response = tiny_http::Response::from_string(body)
+                .with_status_code(status)
+                .with_header(
+                    tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                        .map_err(|_| anyhow::anyhow!("Invalid content type header"))?,
+                );
+            if let Err(e) = request.respond(response) {
+                log::warn!("Unable to answer the request {method} {url}: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the body of a request, up to the maximum size
+    fn read_body(&self, request: &mut tiny_http::Request) -> Result<Vec<u8>, ApiError> {
+        let too_large = || ApiError {
+            status: 413,
+            kind: "payload_too_large",
+            message: format!(
+                "The request body exceeds the limit of {} bytes",
+                self.options.max_body_size
+            ),
+        };
+        if request
+            .body_length()
+            .is_some_and(|length| length > self.options.max_body_size)
+        {
+            return Err(too_large());
+        }
+        let mut body = vec![];
+        request
+            .as_reader()
+            .take(self.options.max_body_size as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| ApiError::invalid_input(format!("Unable to read the body: {e}")))?;
+        if body.len() > self.options.max_body_size {
+            return Err(too_large());
+        }
+        Ok(body)
+    }
+
+    /// Handles a request and returns the JSON body of the response
+    ///
+    /// * `method` - HTTP method of the request
+    /// * `url` - Path of the request, the query string is ignored
+    /// * `body` - Body of the request
+    pub fn handle(&self, method: &str, url: &str, body: &[u8]) -> Result<String, ApiError> {
+        let path = url.split('?').next().unwrap_or_default();
+        let expected_method = match path {
+            "/health" | "/setup/info" => "GET",
+            "/commit" | "/prove" | "/verify" | "/verify-batch" => "POST",
+            _ => {
+                return Err(ApiError {
+                    status: 404,
+                    kind: "not_found",
+                    message: format!("Unknown endpoint \"{path}\""),
+                });
+            }
+        };
+        if method != expected_method {
+            return Err(ApiError {
+                status: 405,
+                kind: "method_not_allowed",
+                message: format!("Endpoint \"{path}\" only accepts {expected_method} requests"),
+            });
+        }
+
+        let response = match path {
+            "/health" => serde_json::json!({ "status": "ok" }),
+            "/setup/info" => to_value(SetupInfoResponse {
+                setup_hash: self.setup.hash(),
+                metadata: &self.setup.metadata,
+            })?,
+            "/commit" => to_value(self.commit(parse_body(body)?)?)?,
+            "/prove" => to_value(self.prove(parse_body(body)?)?)?,
+            "/verify" => to_value(VerifyResponse {
+                verified: self.verify(&parse_body(body)?)?,
+            })?,
+            _ => to_value(self.verify_batch(parse_body(body)?)?)?,
+        };
+        Ok(response.to_string())
+    }
+
+    fn commit(&self, request: PolynomialRequest) -> Result<CommitResponse, ApiError> {
+        let polynomial = self.polynomial(request)?;
+        let commitment = polynomial
+            .commit(&self.setup.artifacts)
+            .map_err(ApiError::internal)?;
+        Ok(CommitResponse {
+            polynomial: polynomial.to_string(),
+            degree: polynomial.degree(),
+            commitment: to_hex(&commitment),
+            setup_hash: self.setup.hash().to_owned(),
+        })
+    }
+
+    fn prove(&self, request: ProveRequest) -> Result<ProveResponse, ApiError> {
+        let polynomial = self.polynomial(request.polynomial)?;
+        let x = self.scalar(&request.x, "x")?;
+
+        let commitment = polynomial
+            .commit(&self.setup.artifacts)
+            .map_err(ApiError::internal)?;
+        let evaluation = polynomial.evaluate(x).map_err(ApiError::internal)?;
+        let proof = evaluation
+            .generate_proof(&polynomial, &self.setup.artifacts)
+            .map_err(ApiError::internal)?;
+
+        Ok(ProveResponse {
+            polynomial: polynomial.to_string(),
+            commitment: to_hex(&commitment),
+            x: evaluation.point.to_string(),
+            y: evaluation.result.to_string(),
+            proof: to_hex(&proof),
+            setup_hash: self.setup.hash().to_owned(),
+        })
+    }
+
+    fn verify(&self, request: &VerifyRequest) -> Result<bool, ApiError> {
+        let commitment = parse_g1_point(&request.commitment, "commitment")?;
+        let proof = parse_g1_point(&request.proof, "proof")?;
+        let evaluation = Evaluation {
+            point: self.scalar(&request.x, "x")?,
+            result: self.scalar(&request.y, "y")?,
+        };
+        evaluation
+            .verify_proof(&proof, &commitment, &self.setup.artifacts)
+            .map_err(ApiError::internal)
+    }
+
+    fn verify_batch(&self, request: VerifyBatchRequest) -> Result<VerifyBatchResponse, ApiError> {
+        if request.openings.len() > MAX_BATCH_SIZE {
+            return Err(ApiError::invalid_input(format!(
+                "A batch holds at most {MAX_BATCH_SIZE} openings, got {}",
+                request.openings.len()
+            )));
+        }
+        let results = request
+            .openings
+            .iter()
+            .enumerate()
+            .map(|(i, opening)| {
+                self.verify(opening).map_err(|e| ApiError {
+                    message: format!("Invalid opening at index {i}: {}", e.message),
+                    ..e
+                })
+            })
+            .collect::<Result<Vec<bool>, _>>()?;
+        Ok(VerifyBatchResponse {
+            verified: results.iter().all(|verified| *verified),
+            results,
+        })
+    }
+
+    /// Parses the polynomial of a request and checks that the setup supports its degree
+    fn polynomial(&self, request: PolynomialRequest) -> Result<Polynomial, ApiError> {
+        let polynomial = match (request.polynomial, request.coefficients) {
+            (Some(expression), None) => {
+                Polynomial::parse_with_max_degree(&expression, self.setup.max_degree()).map_err(
+                    |e| ApiError::invalid_input(format!("Invalid polynomial expression: {e}")),
+                )?
+            }
+            (None, Some(coefficients)) => {
+                let coefficients = coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| self.scalar(c, &format!("coefficient at index {i}")))
+                    .collect::<Result<Vec<Scalar>, _>>()?;
+                Polynomial::try_from(coefficients)
+                    .map_err(|e| ApiError::invalid_input(e.to_string()))?
+            }
+            _ => {
+                return Err(ApiError::invalid_input(
+                    "Exactly one of \"polynomial\" and \"coefficients\" must be given",
+                ));
+            }
+        };
+        if polynomial.degree() > self.setup.metadata.degree {
+            return Err(ApiError::invalid_input(format!(
+                "The polynomial has degree {} while the setup only supports polynomials up to degree {}",
+                polynomial.degree(),
+                self.setup.metadata.degree
+            )));
+        }
+        Ok(polynomial)
+    }
+
+    fn scalar(&self, value: &str, name: &str) -> Result<Scalar, ApiError> {
+        let scalar = if self.options.strict {
+            Scalar::from_str_strict(value)
+        } else {
+            Scalar::from_str(value)
+        };
+        scalar.map_err(|e| ApiError::invalid_input(format!("Invalid {name}: {e}")))
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body)
+        .map_err(|e| ApiError::invalid_input(format!("Invalid request body: {e}")))
+}
+
+fn to_value(value: impl Serialize) -> Result<serde_json::Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::internal(e.into()))
+}
+
+/// Parses a compressed G1 point in hexadecimal, the point must be in the G1 subgroup
+fn parse_g1_point(value: &str, name: &str) -> Result<G1Point, ApiError> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| ApiError::invalid_input(format!("Invalid hexadecimal for the {name}: {e}")))?;
+    G1Point::from_compressed_bytes_in_group(&bytes)
+        .map_err(|e| ApiError::invalid_input(format!("Invalid {name} point: {e}")))
+}
+
+fn to_hex(point: &G1Point) -> String {
+    format!("0x{}", hex::encode(point.to_compressed_bytes()))
+}
@@ -0,0 +1,156 @@
+//! Fiat–Shamir transcript deriving the challenges of non-interactive protocols from the messages of the prover.
+//!
+//! The transcript is a running SHA-256 state. Every absorbed value is framed with its kind, its label and its length,
+//! so that two different sequences of values can not produce the same state. A challenge is derived from the state
+//! and its label, and is absorbed back so that the next challenges differ.
+use sha2::{Digest, Sha256};
+
+use crate::{
+    curves::{G1Point, G2Point},
+    scalar::Scalar,
+};
+
+const PROTOCOL_KIND: u8 = 0;
+const BYTES_KIND: u8 = 1;
+const SCALAR_KIND: u8 = 2;
+const G1_POINT_KIND: u8 = 3;
+const G2_POINT_KIND: u8 = 4;
+const CHALLENGE_KIND: u8 = 5;
+
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    state: Sha256,
+}
+
+impl Transcript {
+    /// Creates a transcript for a protocol
+    ///
+    /// * `protocol` - Label of the protocol, transcripts of different protocols never derive the same challenges
+    pub fn new(protocol: &[u8]) -> Self {
+        let mut transcript = Self {
+            state: Sha256::new(),
+        };
+        transcript.append(PROTOCOL_KIND, b"protocol", protocol);
+        transcript
+    }
+
+    /// Absorbs raw bytes
+    ///
+    /// * `label` - Label of the value in the protocol
+    /// * `bytes` - Absorbed bytes
+    pub fn absorb_bytes(&mut self, label: &[u8], bytes: &[u8]) {
+        self.append(BYTES_KIND, label, bytes);
+    }
+
+    /// Absorbs a scalar, encoded in big endian
+    ///
+    /// * `label` - Label of the value in the protocol
+    /// * `scalar` - Absorbed scalar
+    pub fn absorb_scalar(&mut self, label: &[u8], scalar: &Scalar) {
+        self.append(SCALAR_KIND, label, &scalar.to_be_bytes());
+    }
+
+    /// Absorbs a G1 point, encoded in its compressed form
+    ///
+    /// * `label` - Label of the value in the protocol
+    /// * `point` - Absorbed point
+    pub fn absorb_g1_point(&mut self, label: &[u8], point: &G1Point) {
+        self.append(G1_POINT_KIND, label, &point.to_compressed_bytes());
+    }
+
+    /// Absorbs a G2 point, encoded in its compressed form
+    ///
+    /// * `label` - Label of the value in the protocol
+    /// * `point` - Absorbed point
+    pub fn absorb_g2_point(&mut self, label: &[u8], point: &G2Point) {
+        self.append(G2_POINT_KIND, label, &point.to_compressed_bytes());
+    }
+
+    /// Derives a challenge from everything absorbed so far.
+    ///
+    /// 64 bytes are derived and reduced modulo r, the bias of the reduction is negligible.
+    ///
+    /// * `label` - Label of the challenge in the protocol
+    pub fn squeeze_scalar(&mut self, label: &[u8]) -> Scalar {
+        let mut wide = [0u8; 64];
+        for (counter, half) in wide.chunks_exact_mut(32).enumerate() {
+            let mut state = self.state.clone();
+            frame(&mut state, CHALLENGE_KIND, label, &[counter as u8]);
+            half.copy_from_slice(&state.finalize());
+        }
+        let challenge = Scalar::from_be_bytes_wide(&wide);
+
+        self.absorb_scalar(label, &challenge);
+        challenge
+    }
+
+    fn append(&mut self, kind: u8, label: &[u8], bytes: &[u8]) {
+        frame(&mut self.state, kind, label, bytes);
+    }
+}
+
+/// Feeds a value to the hash state, prefixed with its kind and the lengths of its label and of its content
+fn frame(state: &mut Sha256, kind: u8, label: &[u8], bytes: &[u8]) {
+    state.update([kind]);
+    state.update((label.len() as u64).to_be_bytes());
+    state.update(label);
+    state.update((bytes.len() as u64).to_be_bytes());
+    state.update(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(protocol: &[u8], values: &[(&[u8], &[u8])]) -> Transcript {
+        let mut transcript = Transcript::new(protocol);
+        for (label, bytes) in values {
+            transcript.absorb_bytes(label, bytes);
+        }
+        transcript
+    }
+
+    #[test]
+    fn test_challenges_are_deterministic_and_domain_separated() {
+        let challenge = |mut t: Transcript| t.squeeze_scalar(b"z");
+        let reference = challenge(transcript(b"test", &[(b"a", b"12"), (b"b", b"3")]));
+
+        assert_eq!(
+            reference,
+            challenge(transcript(b"test", &[(b"a", b"12"), (b"b", b"3")]))
+        );
+        // Moving bytes between values, changing a label or the protocol changes the challenge
+        assert_ne!(
+            reference,
+            challenge(transcript(b"test", &[(b"a", b"1"), (b"b", b"23")]))
+        );
+        assert_ne!(
+            reference,
+            challenge(transcript(b"test", &[(b"a", b"12"), (b"c", b"3")]))
+        );
+        assert_ne!(
+            reference,
+            challenge(transcript(b"other", &[(b"a", b"12"), (b"b", b"3")]))
+        );
+
+        // The kind of a value is absorbed along with its encoding
+        let mut as_scalar = Transcript::new(b"test");
+        as_scalar.absorb_scalar(b"a", &Scalar::from_i128(1));
+        let mut as_bytes = Transcript::new(b"test");
+        as_bytes.absorb_bytes(b"a", &Scalar::from_i128(1).to_be_bytes());
+        assert_ne!(
+            as_scalar.squeeze_scalar(b"z"),
+            as_bytes.squeeze_scalar(b"z")
+        );
+    }
+
+    #[test]
+    fn test_successive_challenges_differ() {
+        let mut transcript = Transcript::new(b"test");
+        transcript.absorb_g1_point(b"commitment", &G1Point::from_i128(3));
+        transcript.absorb_g2_point(b"g2", &G2Point::from_i128(3));
+        let first = transcript.squeeze_scalar(b"z");
+        let second = transcript.squeeze_scalar(b"z");
+        assert_ne!(first, second);
+    }
+}
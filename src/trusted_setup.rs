@@ -1,4 +1,13 @@
 use serde::{self, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    num::NonZeroUsize,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub mod ptau;
+pub mod text;
 
 use super::{
     curves,
@@ -34,6 +43,162 @@ pub struct SetupArtifact {
     pub g2: curves::G2Point,
 }
 
+/// Way the setup artifacts have been produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum GenerationMode {
+    /// Generated locally from a single random secret
+    RandomSecret,
+    /// Imported from a `.ptau` file of a Powers of Tau ceremony
+    Ptau { power: u32, ceremony_power: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetupMetadata {
+    /// Creation time, in seconds since the Unix epoch
+    pub created_at: u64,
+    /// Version of the tool that produced the setup
+    pub tool_version: String,
+    /// Maximum degree of the polynomials that can be committed
+    pub degree: u32,
+    /// Number of G2 points
+    pub g2_count: usize,
+    pub generation_mode: GenerationMode,
+    /// SHA-256 of the compressed points, in hexadecimal
+    pub transcript_hash: String,
+    /// True if the secret is known to a single party, the setup must then only be used for testing
+    pub insecure: bool,
+}
+
+/// Setup artifacts along with the metadata describing how they have been produced
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Setup {
+    pub metadata: SetupMetadata,
+    pub artifacts: Vec<SetupArtifact>,
+    /// Points `[γ s^i]_1` blinding the hiding commitments, empty if the setup does not support them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hiding_g1: Vec<G1Point>,
+}
+
+impl Setup {
+    /// Creates a setup from its artifacts, the metadata are derived from the artifacts and the generation mode.
+    ///
+    /// A setup generated from a random secret is flagged as insecure.
+    ///
+    /// * `artifacts` - Setup artifacts, there must be at least one
+    /// * `generation_mode` - Way the artifacts have been produced
+    pub fn new(
+        artifacts: Vec<SetupArtifact>,
+        generation_mode: GenerationMode,
+    ) -> Result<Self, anyhow::Error> {
+        if artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "A setup must contain at least two artifacts, i.e. have a degree of at least 1, got {}",
+                artifacts.len()
+            ));
+        }
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(anyhow::Error::from)?
+            .as_secs();
+
+        Ok(Self {
+            metadata: SetupMetadata {
+                created_at,
+                tool_version: env!("CARGO_PKG_VERSION").to_owned(),
+                degree: (artifacts.len() - 1) as u32,
+                g2_count: artifacts.len(),
+                insecure: generation_mode == GenerationMode::RandomSecret,
+                generation_mode,
+                transcript_hash: transcript_hash(&artifacts, &[]),
+            },
+            artifacts,
+            hiding_g1: vec![],
+        })
+    }
+
+    /// Adds the points of the hiding commitments to the setup, the hash of the setup then covers them
+    ///
+    /// * `hiding_g1` - Points `[γ s^i]_1` for the same powers of the secret as the artifacts
+    pub fn with_hiding_powers(mut self, hiding_g1: Vec<G1Point>) -> Result<Self, anyhow::Error> {
+        if hiding_g1.len() != self.artifacts.len() {
+            return Err(anyhow::anyhow!(
+                "The setup has {} artifacts, got {} hiding points",
+                self.artifacts.len(),
+                hiding_g1.len()
+            ));
+        }
+        self.metadata.transcript_hash = transcript_hash(&self.artifacts, &hiding_g1);
+        self.hiding_g1 = hiding_g1;
+        Ok(self)
+    }
+
+    /// Returns the maximum degree `D` of the polynomials that can be committed with the setup
+    pub fn max_degree(&self) -> u32 {
+        self.metadata.degree
+    }
+
+    /// Returns whether the setup supports hiding commitments
+    pub fn is_hiding(&self) -> bool {
+        !self.hiding_g1.is_empty()
+    }
+
+    /// Returns the hash identifying the setup, downstream artifacts refer to the setup with it
+    pub fn hash(&self) -> &str {
+        &self.metadata.transcript_hash
+    }
+
+    /// Checks that the metadata are consistent with the artifacts
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        let computed_hash = transcript_hash(&self.artifacts, &self.hiding_g1);
+        if computed_hash != self.metadata.transcript_hash {
+            return Err(anyhow::anyhow!(
+                "Setup transcript hash mismatch, metadata contain {} while artifacts hash to {computed_hash}",
+                self.metadata.transcript_hash
+            ));
+        }
+        if self.artifacts.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "Setup must have a degree of at least 1 to verify proofs, got {} artifacts",
+                self.artifacts.len()
+            ));
+        }
+        if self.artifacts.len() != (self.metadata.degree + 1) as usize
+            || self.artifacts.len() != self.metadata.g2_count
+        {
+            return Err(anyhow::anyhow!(
+                "Setup metadata announce degree {} and {} G2 points but {} artifacts are present",
+                self.metadata.degree,
+                self.metadata.g2_count,
+                self.artifacts.len()
+            ));
+        }
+        if self.is_hiding() && self.hiding_g1.len() != self.artifacts.len() {
+            return Err(anyhow::anyhow!(
+                "Setup has {} artifacts but {} hiding points",
+                self.artifacts.len(),
+                self.hiding_g1.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Hashes the compressed G1 points, the compressed G2 points of the artifacts and then the hiding points with SHA-256
+fn transcript_hash(artifacts: &[SetupArtifact], hiding_g1: &[G1Point]) -> String {
+    let mut hasher = Sha256::new();
+    for artifact in artifacts {
+        hasher.update(artifact.g1.to_compressed_bytes());
+    }
+    for artifact in artifacts {
+        hasher.update(artifact.g2.to_compressed_bytes());
+    }
+    for point in hiding_g1 {
+        hasher.update(point.to_compressed_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
 impl Iterator for SetupArtifactsGenerator {
     type Item = SetupArtifact;
 
@@ -77,3 +242,138 @@ impl Iterator for SetupArtifactsGenerator {
         })
     }
 }
+
+#[derive(Debug)]
+pub struct ParallelSetupArtifactsGenerator {
+    secret: Scalar,
+    threads: NonZeroUsize,
+}
+
+impl ParallelSetupArtifactsGenerator {
+    /// Creates a new parallel generator for trusted setup artifacts, the work is split over the available parallelism
+    ///
+    /// * `secret` - Secret used to generate artifacts, in big endian bytes
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self {
+            secret: Scalar::from_be_bytes(secret),
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+
+    /// Sets the number of threads used for the generation
+    ///
+    /// * `threads` - Number of threads
+    pub fn with_threads(mut self, threads: NonZeroUsize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Generates the artifacts for the powers `0, ..., count - 1` of the secret.
+    ///
+    /// The powers of the secret are computed sequentially first, the multiplications of the generators
+    /// are then shared between the threads using the fixed-base tables of the generators.
+    ///
+    /// * `count` - Number of artifacts to generate
+    pub fn generate(&self, count: usize) -> Vec<SetupArtifact> {
+        let mut s_powers = Vec::with_capacity(count);
+        let mut current_s_powered = Scalar::from_i128(1);
+        for _ in 0..count {
+            let next_s_powered = current_s_powered.mul(&self.secret);
+            s_powers.push(current_s_powered);
+            current_s_powered = next_s_powered;
+        }
+
+        let chunk_size = count.div_ceil(self.threads.get()).max(1);
+        thread::scope(|scope| {
+            let handles: Vec<_> = s_powers
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|s_powered| SetupArtifact {
+                                g1: G1Point::from_scalar(s_powered.clone()),
+                                g2: G2Point::from_scalar(s_powered.clone()),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .expect("Setup artifacts generation thread has panicked")
+                })
+                .collect()
+        })
+    }
+
+    /// Generates the points `[γ s^i]_1` of the hiding commitments for the powers `0, ..., count - 1` of the secret
+    ///
+    /// * `count` - Number of points to generate, the same as the number of artifacts
+    /// * `gamma` - Secret blinding factor `γ`, in big endian bytes, independent from the secret of the powers
+    pub fn generate_hiding_powers(&self, count: usize, gamma: [u8; 32]) -> Vec<G1Point> {
+        let mut current = Scalar::from_be_bytes(gamma);
+        let mut points = Vec::with_capacity(count);
+        for _ in 0..count {
+            let next = current.mul(&self.secret);
+            points.push(G1Point::from_scalar(current));
+            current = next;
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_metadata() {
+        let s_bytes: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let artifacts = ParallelSetupArtifactsGenerator::new(s_bytes).generate(5);
+        let setup = Setup::new(artifacts, GenerationMode::RandomSecret).unwrap();
+
+        assert_eq!(setup.metadata.degree, 4);
+        assert_eq!(setup.metadata.g2_count, 5);
+        assert!(setup.metadata.insecure);
+        assert_eq!(setup.hash().len(), 64);
+        setup.validate().unwrap();
+
+        let other_artifacts = ParallelSetupArtifactsGenerator::new([1; 32]).generate(5);
+        let other_setup = Setup::new(other_artifacts, GenerationMode::RandomSecret).unwrap();
+        assert_ne!(setup.hash(), other_setup.hash());
+
+        let tampered_setup = Setup {
+            metadata: setup.metadata.clone(),
+            artifacts: other_setup.artifacts,
+            hiding_g1: vec![],
+        };
+        assert!(tampered_setup.validate().is_err());
+        // A single artifact can not verify any proof
+        let degree_zero = ParallelSetupArtifactsGenerator::new([1; 32]).generate(1);
+        assert!(Setup::new(degree_zero, GenerationMode::RandomSecret).is_err());
+    }
+
+    #[test]
+    fn test_parallel_generation_matches_sequential_generation() {
+        let mut s_bytes = [0; 32];
+        s_bytes.copy_from_slice(&(0..32).collect::<Vec<u8>>());
+
+        let sequential_artifacts: Vec<_> = SetupArtifactsGenerator::new(s_bytes).take(20).collect();
+        for threads in [1, 3, 8] {
+            let parallel_artifacts = ParallelSetupArtifactsGenerator::new(s_bytes)
+                .with_threads(NonZeroUsize::new(threads).unwrap())
+                .generate(20);
+
+            assert_eq!(parallel_artifacts.len(), sequential_artifacts.len());
+            for (parallel, sequential) in parallel_artifacts.iter().zip(&sequential_artifacts) {
+                assert_eq!(parallel.g1, sequential.g1);
+                assert_eq!(parallel.g2, sequential.g2);
+            }
+        }
+    }
+}
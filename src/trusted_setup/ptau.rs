@@ -0,0 +1,414 @@
+//! Reader for the `.ptau` files produced by snarkjs and the Perpetual Powers of Tau ceremonies.
+//!
+//! A `.ptau` file starts with the magic `ptau`, a version and a number of sections. Each section is
+//! introduced by its type (`u32`) and its size in bytes (`u64`). All integers are little endian.
+//!
+//! The sections used here are:
+//! - `1` - header: size of a base field element `n8`, the base field modulus `q`, the power and the ceremony power,
+//! - `2` - `tauG1`: the points `s^i * G1` for `i = 0, ..., 2^(power + 1) - 2`,
+//! - `3` - `tauG2`: the points `s^i * G2` for `i = 0, ..., 2^power - 1`.
+//!
+//! Points are stored uncompressed, each coordinate is a base field element in little endian Montgomery form.
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom},
+};
+
+use crate::curves::{G1Point, G2Point};
+
+use super::SetupArtifact;
+
+const MAGIC: &[u8; 4] = b"ptau";
+const VERSION: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+
+/// Size in bytes of a BLS12-381 base field element
+const FP_BYTES: usize = 48;
+const G1_BYTES: usize = 2 * FP_BYTES;
+const G2_BYTES: usize = 4 * FP_BYTES;
+
+/// BLS12-381 base field modulus in big endian hexadecimal
+const Q_AS_HEX: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtauHeader {
+    /// Power of the file, the file contains `2^power` points in `tauG2`
+    pub power: u32,
+    /// Power of the ceremony the file has been extracted from
+    pub ceremony_power: u32,
+}
+
+impl PtauHeader {
+    /// Number of `tauG1` points in the file
+    pub fn tau_g1_count(&self) -> usize {
+        (1 << (self.power + 1)) - 1
+    }
+
+    /// Number of `tauG2` points in the file
+    pub fn tau_g2_count(&self) -> usize {
+        1 << self.power
+    }
+}
+
+#[derive(Debug)]
+pub struct PtauFile<R> {
+    reader: R,
+    header: PtauHeader,
+    /// Position of the data of each section, as offset and size in bytes
+    sections: HashMap<u32, (u64, u64)>,
+}
+
+impl<R: Read + Seek> PtauFile<R> {
+    /// Opens a `.ptau` file, the header is read and validated against the BLS12-381 curve.
+    ///
+    /// * `reader` - Reader on the content of the file
+    pub fn open(mut reader: R) -> Result<Self, anyhow::Error> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(anyhow::anyhow!(
+                "Invalid file type, expected magic {MAGIC:?}, got {magic:?}"
+            ));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported ptau version, expected {VERSION}, got {version}"
+            ));
+        }
+
+        let sections_count = read_u32(&mut reader)?;
+        let file_start = reader.stream_position()?;
+        let file_end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(file_start))?;
+        let mut sections = HashMap::new();
+        for _ in 0..sections_count {
+            let section_type = read_u32(&mut reader)?;
+            let section_size = read_u64(&mut reader)?;
+            let section_offset = reader.stream_position()?;
+            if section_offset.saturating_add(section_size) > file_end {
+                return Err(anyhow::anyhow!(
+                    "Section {section_type} of size {section_size} goes beyond the end of the file"
+                ));
+            }
+            if sections
+                .insert(section_type, (section_offset, section_size))
+                .is_some()
+            {
+                return Err(anyhow::anyhow!(
+                    "Section {section_type} is present more than once"
+                ));
+            }
+            reader.seek(SeekFrom::Current(section_size as i64))?;
+        }
+
+        let (header_offset, header_size) = *sections
+            .get(&HEADER_SECTION)
+            .ok_or_else(|| anyhow::anyhow!("Header section is missing"))?;
+        reader.seek(SeekFrom::Start(header_offset))?;
+
+        let n8 = read_u32(&mut reader)? as usize;
+        if n8 != FP_BYTES {
+            return Err(anyhow::anyhow!(
+                "Unsupported curve, expected base field elements of {FP_BYTES} bytes, got {n8}"
+            ));
+        }
+        if header_size != (n8 + 12) as u64 {
+            return Err(anyhow::anyhow!(
+                "Invalid header section size, expected {}, got {header_size}",
+                n8 + 12
+            ));
+        }
+        let mut q_le_bytes = [0u8; FP_BYTES];
+        reader.read_exact(&mut q_le_bytes)?;
+        q_le_bytes.reverse();
+        if hex_from_be_bytes(&q_le_bytes) != Q_AS_HEX {
+            return Err(anyhow::anyhow!(
+                "Unsupported curve, the base field modulus is not the one of BLS12-381"
+            ));
+        }
+
+        let power = read_u32(&mut reader)?;
+        let ceremony_power = read_u32(&mut reader)?;
+        if power == 0 || power > 32 {
+            return Err(anyhow::anyhow!(
+                "Invalid power, expected a value between 1 and 32, got {power}"
+            ));
+        }
+        if ceremony_power < power {
+            return Err(anyhow::anyhow!(
+                "Invalid power, power {power} is larger than the ceremony power {ceremony_power}"
+            ));
+        }
+        let header = PtauHeader {
+            power,
+            ceremony_power,
+        };
+
+        for (section_type, expected_size) in [
+            (TAU_G1_SECTION, header.tau_g1_count() * G1_BYTES),
+            (TAU_G2_SECTION, header.tau_g2_count() * G2_BYTES),
+        ] {
+            let (_, size) = sections
+                .get(&section_type)
+                .ok_or_else(|| anyhow::anyhow!("Section {section_type} is missing"))?;
+            if *size != expected_size as u64 {
+                return Err(anyhow::anyhow!(
+                    "Invalid size for section {section_type}, expected {expected_size} for power {power}, got {size}"
+                ));
+            }
+        }
+
+        Ok(Self {
+            reader,
+            header,
+            sections,
+        })
+    }
+
+    /// Returns the header of the file
+    pub fn header(&self) -> &PtauHeader {
+        &self.header
+    }
+
+    /// Reads the first setup artifacts of the file.
+    ///
+    /// An artifact pairs the `tauG1` and `tauG2` points of the same power, there are at most `2^power` artifacts.
+    /// Each point is checked to be on the curve and in the expected subgroup.
+    ///
+    /// * `count` - Number of artifacts to read
+    pub fn setup_artifacts(&mut self, count: usize) -> Result<Vec<SetupArtifact>, anyhow::Error> {
+        if count > self.header.tau_g2_count() {
+            return Err(anyhow::anyhow!(
+                "Unable to read {count} artifacts, the file only contains {} powers in G2",
+                self.header.tau_g2_count()
+            ));
+        }
+
+        let g1_points = self.read_section_points(TAU_G1_SECTION, count, G1_BYTES, |bytes| {
+            G1Point::try_from(blst::blst_p1_affine {
+                x: fp_from_montgomery_le_bytes(&bytes[..FP_BYTES]),
+                y: fp_from_montgomery_le_bytes(&bytes[FP_BYTES..]),
+            })
+        })?;
+        let g2_points = self.read_section_points(TAU_G2_SECTION, count, G2_BYTES, |bytes| {
+            G2Point::try_from(blst::blst_p2_affine {
+                x: fp2_from_montgomery_le_bytes(&bytes[..2 * FP_BYTES]),
+                y: fp2_from_montgomery_le_bytes(&bytes[2 * FP_BYTES..]),
+            })
+        })?;
+
+        Ok(g1_points
+            .into_iter()
+            .zip(g2_points)
+            .map(|(g1, g2)| SetupArtifact { g1, g2 })
+            .collect())
+    }
+
+    fn read_section_points<T>(
+        &mut self,
+        section_type: u32,
+        count: usize,
+        point_size: usize,
+        parse: impl Fn(&[u8]) -> Result<T, anyhow::Error>,
+    ) -> Result<Vec<T>, anyhow::Error> {
+        let (offset, _) = self.sections[&section_type];
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        let mut bytes = vec![0u8; point_size];
+        let mut points = Vec::with_capacity(count);
+        for i in 0..count {
+            self.reader.read_exact(&mut bytes)?;
+            let point = parse(&bytes).map_err(|e| {
+                anyhow::anyhow!("Invalid point at index {i} of section {section_type}: {e}")
+            })?;
+            points.push(point);
+        }
+        Ok(points)
+    }
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, anyhow::Error> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, anyhow::Error> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn hex_from_be_bytes(a: &[u8]) -> String {
+    a.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Builds a base field element from its little endian Montgomery form.
+///
+/// Both snarkjs and blst use `R = 2^384` for the Montgomery form, the limbs can be used as is.
+fn fp_from_montgomery_le_bytes(bytes: &[u8]) -> blst::blst_fp {
+    let mut fp = blst::blst_fp::default();
+    for (limb, chunk) in fp.l.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut limb_bytes = [0u8; 8];
+        limb_bytes.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(limb_bytes);
+    }
+    fp
+}
+
+fn fp2_from_montgomery_le_bytes(bytes: &[u8]) -> blst::blst_fp2 {
+    blst::blst_fp2 {
+        fp: [
+            fp_from_montgomery_le_bytes(&bytes[..FP_BYTES]),
+            fp_from_montgomery_le_bytes(&bytes[FP_BYTES..]),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::trusted_setup::SetupArtifactsGenerator;
+
+    fn fp_to_montgomery_le_bytes(fp: &blst::blst_fp) -> Vec<u8> {
+        fp.l.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+    }
+
+    fn write_section(out: &mut Vec<u8>, section_type: u32, data: &[u8]) {
+        out.extend_from_slice(&section_type.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+
+    /// Generates a `.ptau` fixture of the given power with the same layout as snarkjs
+    fn generate_ptau_fixture(power: u32, q_be_hex: &str) -> Vec<u8> {
+        let ptau_header = PtauHeader {
+            power,
+            ceremony_power: power,
+        };
+        let s_bytes: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let artifacts: Vec<SetupArtifact> = SetupArtifactsGenerator::new(s_bytes)
+            .take(ptau_header.tau_g1_count())
+            .collect();
+
+        let mut header = vec![];
+        header.extend_from_slice(&(FP_BYTES as u32).to_le_bytes());
+        let mut q_le_bytes = hex::decode(q_be_hex).unwrap();
+        q_le_bytes.reverse();
+        header.extend_from_slice(&q_le_bytes);
+        header.extend_from_slice(&power.to_le_bytes());
+        header.extend_from_slice(&power.to_le_bytes());
+
+        let mut tau_g1 = vec![];
+        for artifact in artifacts.iter() {
+            let mut affine = blst::blst_p1_affine::default();
+            unsafe { blst::blst_p1_to_affine(&mut affine, &*artifact.g1) };
+            tau_g1.extend(fp_to_montgomery_le_bytes(&affine.x));
+            tau_g1.extend(fp_to_montgomery_le_bytes(&affine.y));
+        }
+        let mut tau_g2 = vec![];
+        for artifact in artifacts.iter().take(ptau_header.tau_g2_count()) {
+            let mut affine = blst::blst_p2_affine::default();
+            unsafe { blst::blst_p2_to_affine(&mut affine, &*artifact.g2) };
+            for fp in affine.x.fp.iter().chain(affine.y.fp.iter()) {
+                tau_g2.extend(fp_to_montgomery_le_bytes(fp));
+            }
+        }
+
+        let mut out = vec![];
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.extend_from_slice(&3u32.to_le_bytes());
+        write_section(&mut out, HEADER_SECTION, &header);
+        write_section(&mut out, TAU_G1_SECTION, &tau_g1);
+        write_section(&mut out, TAU_G2_SECTION, &tau_g2);
+        out
+    }
+
+    #[test]
+    fn test_read_ptau_fixture() {
+        let fixture = generate_ptau_fixture(3, Q_AS_HEX);
+        let mut ptau = PtauFile::open(Cursor::new(fixture)).unwrap();
+        assert_eq!(
+            ptau.header(),
+            &PtauHeader {
+                power: 3,
+                ceremony_power: 3
+            }
+        );
+
+        let s_bytes: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let expected_artifacts: Vec<SetupArtifact> =
+            SetupArtifactsGenerator::new(s_bytes).take(8).collect();
+        let artifacts = ptau.setup_artifacts(8).unwrap();
+        assert_eq!(artifacts.len(), 8);
+        for (artifact, expected) in artifacts.iter().zip(&expected_artifacts) {
+            assert_eq!(artifact.g1, expected.g1);
+            assert_eq!(artifact.g2, expected.g2);
+        }
+
+        assert!(
+            ptau.setup_artifacts(9).is_err(),
+            "reading more artifacts than the G2 powers must fail"
+        );
+    }
+
+    #[test]
+    fn test_read_ptau_file_with_known_secret() {
+        // Written by `tests/fixtures/generate_ptau.py` with its own curve arithmetic, in the layout of snarkjs
+        // with all the sections of a contribution and the secret s = 2
+        let fixture = include_bytes!("../../tests/fixtures/power_1.ptau");
+        let mut ptau = PtauFile::open(Cursor::new(fixture)).unwrap();
+        assert_eq!(
+            ptau.header(),
+            &PtauHeader {
+                power: 1,
+                ceremony_power: 1
+            }
+        );
+
+        let artifacts = ptau.setup_artifacts(2).unwrap();
+        for (i, artifact) in artifacts.iter().enumerate() {
+            assert_eq!(artifact.g1, G1Point::from_i128(1 << i));
+            assert_eq!(artifact.g2, G2Point::from_i128(1 << i));
+        }
+        assert_eq!(
+            hex::encode(artifacts[1].g1.to_compressed_bytes()),
+            "a572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e"
+        );
+        assert!(ptau.setup_artifacts(3).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_ptau_files() {
+        let mut wrong_magic = generate_ptau_fixture(1, Q_AS_HEX);
+        wrong_magic[0] = b'x';
+        assert!(PtauFile::open(Cursor::new(wrong_magic)).is_err());
+
+        // Base field modulus of BN254, used by most snarkjs ceremonies
+        let bn254_q = "0000000000000000000000000000000030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+        assert!(PtauFile::open(Cursor::new(generate_ptau_fixture(1, bn254_q))).is_err());
+
+        let mut truncated = generate_ptau_fixture(2, Q_AS_HEX);
+        truncated.truncate(truncated.len() - G2_BYTES);
+        assert!(PtauFile::open(Cursor::new(truncated)).is_err());
+
+        let mut wrong_power = generate_ptau_fixture(2, Q_AS_HEX);
+        // Power is located after the magic, version, sections count, section header, n8 and q
+        let power_offset = 4 + 4 + 4 + 12 + 4 + FP_BYTES;
+        wrong_power[power_offset..power_offset + 4].copy_from_slice(&3u32.to_le_bytes());
+        assert!(PtauFile::open(Cursor::new(wrong_power)).is_err());
+
+        let mut corrupted_point = generate_ptau_fixture(1, Q_AS_HEX);
+        let first_g1_offset = 4 + 4 + 4 + 12 + 12 + FP_BYTES + 12;
+        corrupted_point[first_g1_offset] ^= 1;
+        let mut ptau = PtauFile::open(Cursor::new(corrupted_point)).unwrap();
+        assert!(ptau.setup_artifacts(1).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Text format of the trusted setups consumed by c-kzg-4844 and go-kzg-4844.
+//!
+//! The file starts with the number of G1 points and the number of G2 points, one per line.
+//! The points follow, one compressed point in hexadecimal per line, in one of the two layouts:
+//! - monomial: the `n_g1` points `s^i * G1` then the `n_g2` points `s^i * G2`,
+//! - Lagrange: the `n_g1` points `L_i(s) * G1` over the roots of unity, the `n_g2` points `s^i * G2`
+//!   then the `n_g1` points `s^i * G1`. This is the layout of the `trusted_setup.txt` of c-kzg-4844.
+//!
+//! The Lagrange points are written in the natural order of the domain `1, ω, ..., ω^(n_g1 - 1)`.
+//! The layout is not recorded in the file, the reader is told which one to expect.
+use std::io::{BufRead, Write};
+
+use crate::{
+    curves::{G1Point, G2Point},
+    domain::Domain,
+};
+
+use super::SetupArtifact;
+
+#[derive(Debug, Clone)]
+pub struct TextSetup {
+    pub g1_monomial: Vec<G1Point>,
+    pub g1_lagrange: Option<Vec<G1Point>>,
+    pub g2_monomial: Vec<G2Point>,
+}
+
+impl TextSetup {
+    /// Creates a text setup from setup artifacts
+    ///
+    /// * `setup_artifacts` - Setup artifacts, all their G1 points are used
+    /// * `g2_count` - Number of G2 points to keep, c-kzg-4844 uses 65 of them
+    /// * `with_lagrange` - Whether the G1 points in Lagrange form must be computed, the number of artifacts must then be a power of two
+    pub fn from_setup_artifacts(
+        setup_artifacts: &[SetupArtifact],
+        g2_count: usize,
+        with_lagrange: bool,
+    ) -> Result<Self, anyhow::Error> {
+        if g2_count > setup_artifacts.len() {
+            return Err(anyhow::anyhow!(
+                "Unable to export {g2_count} G2 points, the setup only contains {}",
+                setup_artifacts.len()
+            ));
+        }
+
+        let g1_monomial: Vec<G1Point> = setup_artifacts.iter().map(|a| a.g1.clone()).collect();
+        let g2_monomial = setup_artifacts[..g2_count]
+            .iter()
+            .map(|a| a.g2.clone())
+            .collect();
+
+        let g1_lagrange = if with_lagrange {
+            let domain = Domain::new(g1_monomial.len()).map_err(|e| {
+                anyhow::anyhow!("Lagrange points require a power of two G1 points: {e}")
+            })?;
+            Some(domain.ifft(&g1_monomial)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+
+    /// Returns the setup artifacts, one per power available in both G1 and G2.
+    ///
+    /// The G1 points whose power has no G2 point are dropped, e.g. the `trusted_setup.txt` of c-kzg-4844 with 4096 G1
+    /// points and 65 G2 points gives 65 artifacts, i.e. a setup of degree 64.
+    pub fn setup_artifacts(&self) -> Vec<SetupArtifact> {
+        self.g1_monomial
+            .iter()
+            .zip(&self.g2_monomial)
+            .map(|(g1, g2)| SetupArtifact {
+                g1: g1.clone(),
+                g2: g2.clone(),
+            })
+            .collect()
+    }
+
+    /// Writes the setup in the text format
+    ///
+    /// * `writer` - Destination of the setup
+    pub fn write(&self, mut writer: impl Write) -> Result<(), anyhow::Error> {
+        writeln!(writer, "{}", self.g1_monomial.len())?;
+        writeln!(writer, "{}", self.g2_monomial.len())?;
+
+        let write_g1 = |writer: &mut dyn Write, points: &[G1Point]| -> std::io::Result<()> {
+            for point in points {
+                writeln!(writer, "{}", hex::encode(point.to_compressed_bytes()))?;
+            }
+            Ok(())
+        };
+
+        match &self.g1_lagrange {
+            Some(g1_lagrange) => write_g1(&mut writer, g1_lagrange)?,
+            None => write_g1(&mut writer, &self.g1_monomial)?,
+        };
+        for point in &self.g2_monomial {
+            writeln!(writer, "{}", hex::encode(point.to_compressed_bytes()))?;
+        }
+        if self.g1_lagrange.is_some() {
+            write_g1(&mut writer, &self.g1_monomial)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a setup in the text format, the number of points must match the expected layout.
+    ///
+    /// Each point is checked to be in the expected subgroup.
+    ///
+    /// * `reader` - Source of the setup
+    /// * `with_lagrange` - Whether the file holds the G1 points in Lagrange form, as the `trusted_setup.txt` of c-kzg-4844
+    pub fn read(reader: impl BufRead, with_lagrange: bool) -> Result<Self, anyhow::Error> {
+        let lines = reader
+            .lines()
+            .map(|line| line.map(|l| l.trim().to_owned()))
+            .filter(|line| !matches!(line, Ok(l) if l.is_empty()))
+            .collect::<Result<Vec<String>, _>>()?;
+        if lines.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "Missing number of points, the file must start with the number of G1 and G2 points"
+            ));
+        }
+
+        let g1_count: usize = lines[0]
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid number of G1 points {:?}: {e}", lines[0]))?;
+        let g2_count: usize = lines[1]
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid number of G2 points {:?}: {e}", lines[1]))?;
+        let points = &lines[2..];
+
+        let (layout, expected_lines) = if with_lagrange {
+            ("Lagrange", 2 * g1_count + g2_count)
+        } else {
+            ("monomial", g1_count + g2_count)
+        };
+        if points.len() != expected_lines {
+            return Err(anyhow::anyhow!(
+                "Invalid number of points for {g1_count} G1 points and {g2_count} G2 points in the {layout} layout, expected {expected_lines} lines of points, got {}",
+                points.len()
+            ));
+        }
+
+        let (g1_first, rest) = points.split_at(g1_count);
+        let (g2_monomial, g1_last) = rest.split_at(g2_count);
+
+        let g2_monomial = parse_points(g2_monomial, "G2", G2Point::from_compressed_bytes_in_group)?;
+        let g1_first = parse_points(g1_first, "G1", G1Point::from_compressed_bytes_in_group)?;
+        let (g1_monomial, g1_lagrange) = if with_lagrange {
+            (
+                parse_points(g1_last, "G1", G1Point::from_compressed_bytes_in_group)?,
+                Some(g1_first),
+            )
+        } else {
+            (g1_first, None)
+        };
+
+        Ok(Self {
+            g1_monomial,
+            g1_lagrange,
+            g2_monomial,
+        })
+    }
+}
+
+/// Parses lines of compressed points in hexadecimal, with or without `0x` prefix
+///
+/// * `lines` - Lines of compressed points
+/// * `group` - Name of the group, used in error messages
+/// * `parse` - Decompresses and validates a point
+fn parse_points<T>(
+    lines: &[String],
+    group: &str,
+    parse: impl Fn(&[u8]) -> Result<T, anyhow::Error>,
+) -> Result<Vec<T>, anyhow::Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let bytes = hex::decode(line.trim_start_matches("0x"))
+                .map_err(|e| anyhow::anyhow!("Invalid hexadecimal for {group} point {i}: {e}"))?;
+            parse(&bytes).map_err(|e| anyhow::anyhow!("Invalid {group} point {i}: {e}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        polynomial::Polynomial, scalar::Scalar, trusted_setup::ParallelSetupArtifactsGenerator,
+    };
+
+    fn generate_setup_artifacts(count: usize) -> Vec<SetupArtifact> {
+        let s_bytes: [u8; 32] = core::array::from_fn(|i| i as u8);
+        ParallelSetupArtifactsGenerator::new(s_bytes).generate(count)
+    }
+
+    fn assert_same_setup_artifacts(a: &[SetupArtifact], b: &[SetupArtifact]) {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(a.g1, b.g1);
+            assert_eq!(a.g2, b.g2);
+        }
+    }
+
+    #[test]
+    fn test_text_setup_round_trip() {
+        let setup_artifacts = generate_setup_artifacts(16);
+
+        for with_lagrange in [false, true] {
+            let text_setup =
+                TextSetup::from_setup_artifacts(&setup_artifacts, 5, with_lagrange).unwrap();
+            let mut written = vec![];
+            text_setup.write(&mut written).unwrap();
+
+            let lines = String::from_utf8(written.clone()).unwrap();
+            let mut lines = lines.lines();
+            assert_eq!(lines.next(), Some("16"));
+            assert_eq!(lines.next(), Some("5"));
+
+            let read_setup = TextSetup::read(written.as_slice(), with_lagrange).unwrap();
+            assert_eq!(read_setup.g1_monomial, text_setup.g1_monomial);
+            assert_eq!(read_setup.g2_monomial, text_setup.g2_monomial);
+            assert_eq!(read_setup.g1_lagrange, text_setup.g1_lagrange);
+            // The G1 points above the 5 G2 points are dropped
+            assert_same_setup_artifacts(&read_setup.setup_artifacts(), &setup_artifacts[..5]);
+
+            // The other layout is rejected instead of being guessed
+            assert!(TextSetup::read(written.as_slice(), !with_lagrange).is_err());
+        }
+    }
+
+    #[test]
+    fn test_lagrange_points_commit_to_evaluations() {
+        let setup_artifacts = generate_setup_artifacts(8);
+        let text_setup = TextSetup::from_setup_artifacts(&setup_artifacts, 2, true).unwrap();
+        let g1_lagrange = text_setup.g1_lagrange.unwrap();
+
+        let polynomial = Polynomial::try_from(vec![3, 1, 4, 1, 5, 9, 2, 6]).unwrap();
+        let domain = Domain::new(8).unwrap();
+        let mut commitment_from_evaluations = G1Point::from_i128(0);
+        for (x, lagrange_point) in domain.elements().into_iter().zip(&g1_lagrange) {
+            let y: Scalar = polynomial.evaluate(x).unwrap().result;
+            commitment_from_evaluations = commitment_from_evaluations.add(&lagrange_point.mult(&y));
+        }
+
+        assert_eq!(
+            commitment_from_evaluations,
+            polynomial.commit(&setup_artifacts).unwrap()
+        );
+        assert!(TextSetup::from_setup_artifacts(&setup_artifacts[..6], 2, true).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_text_setup() {
+        let setup_artifacts = generate_setup_artifacts(4);
+        let mut written = vec![];
+        TextSetup::from_setup_artifacts(&setup_artifacts, 2, false)
+            .unwrap()
+            .write(&mut written)
+            .unwrap();
+        let content = String::from_utf8(written).unwrap();
+
+        let missing_point = content.lines().take(5).collect::<Vec<_>>().join("\n");
+        assert!(TextSetup::read(missing_point.as_bytes(), false).is_err());
+
+        let corrupted: Vec<String> = content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                if i == 2 {
+                    "00".repeat(48)
+                } else {
+                    line.to_owned()
+                }
+            })
+            .collect();
+        assert!(TextSetup::read(corrupted.join("\n").as_bytes(), false).is_err());
+    }
+}
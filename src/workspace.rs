@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::artifacts::{CommitmentArtifact, EvaluationArtifact, read_artifact, write_artifact};
+
+/// Name of the index file, located at the root of the workspace folder
+const INDEX_FILE_NAME: &str = "workspace.json";
+const COMMITMENTS_FOLDER_NAME: &str = "commitments";
+const EVALUATIONS_FOLDER_NAME: &str = "evaluations";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceEntry {
+    /// Path of the commitment artifact, relative to the workspace folder
+    pub commitment_path: PathBuf,
+    /// Path of the evaluation artifact, relative to the workspace folder, if the polynomial has been evaluated
+    pub evaluation_path: Option<PathBuf>,
+    /// Degree of the committed polynomial
+    pub degree: u32,
+    /// Compressed commitment in hexadecimal
+    pub commitment: String,
+    /// Hash of the setup used for the commitment
+    pub setup_hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WorkspaceIndex {
+    commitments: BTreeMap<String, WorkspaceEntry>,
+}
+
+/// Folder of artifacts where commitments are stored by name.
+///
+/// The index file maps each name to its commitment and evaluation artifacts.
+#[derive(Debug)]
+pub struct Workspace {
+    dir: PathBuf,
+    index: WorkspaceIndex,
+}
+
+impl Workspace {
+    /// Opens the workspace of a folder, the workspace is empty if the folder has no index yet
+    ///
+    /// * `dir` - Folder of the workspace
+    pub fn open(dir: &Path) -> Result<Self, anyhow::Error> {
+        let index_path = dir.join(INDEX_FILE_NAME);
+        let index = if fs::exists(&index_path)? {
+            read_artifact(&index_path, "Workspace index has not been found.")?
+        } else {
+            WorkspaceIndex::default()
+        };
+        Ok(Self {
+            dir: dir.to_owned(),
+            index,
+        })
+    }
+
+    /// Returns the entries of the workspace, sorted by name
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &WorkspaceEntry)> {
+        self.index.commitments.iter()
+    }
+
+    /// Returns the entry of a commitment
+    ///
+    /// * `name` - Name of the commitment
+    pub fn entry(&self, name: &str) -> Result<&WorkspaceEntry, anyhow::Error> {
+        self.index.commitments.get(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No commitment named \"{name}\" in the workspace \"{}\", commit to a polynomial with this name beforehand.",
+                self.dir.display()
+            )
+        })
+    }
+
+    /// Stores a commitment artifact under a name and records it in the index.
+    ///
+    /// Any previous commitment with the same name is replaced, along with its evaluation.
+    ///
+    /// * `name` - Name of the commitment
+    /// * `artifact` - Commitment artifact to store
+    pub fn record_commitment(
+        &mut self,
+        name: &str,
+        artifact: &CommitmentArtifact,
+    ) -> Result<PathBuf, anyhow::Error> {
+        validate_name(name)?;
+        let commitment_path = Path::new(COMMITMENTS_FOLDER_NAME).join(format!("{name}.json"));
+        let absolute_path = self.dir.join(&commitment_path);
+        write_artifact(&absolute_path, artifact)?;
+
+        if let Some(previous_evaluation) = self
+            .index
+            .commitments
+            .get(name)
+            .and_then(|entry| entry.evaluation_path.as_ref())
+        {
+            let previous_evaluation = self.dir.join(previous_evaluation);
+            if fs::exists(&previous_evaluation)? {
+                fs::remove_file(previous_evaluation)?;
+            }
+        }
+
+        self.index.commitments.insert(
+            name.to_owned(),
+            WorkspaceEntry {
+                commitment_path,
+                evaluation_path: None,
+                degree: artifact.polynomial.degree(),
+                commitment: hex::encode(artifact.commitment.to_compressed_bytes()),
+                setup_hash: artifact.setup_hash.clone(),
+            },
+        );
+        self.save()?;
+        Ok(absolute_path)
+    }
+
+    /// Stores the evaluation artifact of a named commitment and records it in the index
+    ///
+    /// * `name` - Name of the commitment
+    /// * `artifact` - Evaluation artifact to store
+    pub fn record_evaluation(
+        &mut self,
+        name: &str,
+        artifact: &EvaluationArtifact,
+    ) -> Result<PathBuf, anyhow::Error> {
+        self.entry(name)?;
+        let evaluation_path = Path::new(EVALUATIONS_FOLDER_NAME).join(format!("{name}.json"));
+        let absolute_path = self.dir.join(&evaluation_path);
+        write_artifact(&absolute_path, artifact)?;
+
+        if let Some(entry) = self.index.commitments.get_mut(name) {
+            entry.evaluation_path = Some(evaluation_path);
+        }
+        self.save()?;
+        Ok(absolute_path)
+    }
+
+    /// Reads the commitment artifact of a name
+    ///
+    /// * `name` - Name of the commitment
+    pub fn read_commitment(&self, name: &str) -> Result<CommitmentArtifact, anyhow::Error> {
+        let entry = self.entry(name)?;
+        read_artifact(
+            &self.dir.join(&entry.commitment_path),
+            &format!(
+                "Commitment artifact of \"{name}\" has not been found, generate it beforehand."
+            ),
+        )
+    }
+
+    /// Reads the evaluation artifact of a name
+    ///
+    /// * `name` - Name of the commitment
+    pub fn read_evaluation(&self, name: &str) -> Result<EvaluationArtifact, anyhow::Error> {
+        let evaluation_path = self.entry(name)?.evaluation_path.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "The commitment \"{name}\" has not been evaluated, evaluate it beforehand."
+            )
+        })?;
+        read_artifact(
+            &self.dir.join(evaluation_path),
+            &format!(
+                "Evaluation artifact of \"{name}\" has not been found, generate it beforehand."
+            ),
+        )
+    }
+
+    fn save(&self) -> Result<(), anyhow::Error> {
+        write_artifact(&self.dir.join(INDEX_FILE_NAME), &self.index)
+    }
+}
+
+/// Names are used as file names, they are restricted to ASCII alphanumeric characters, `-`, `_` and `.`
+fn validate_name(name: &str) -> Result<(), anyhow::Error> {
+    let is_valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if !is_valid {
+        return Err(anyhow::anyhow!(
+            "Invalid name \"{name}\", only ASCII letters, digits, '-', '_' and '.' are allowed and the name must not start with '.'"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        artifacts::Opening,
+        polynomial::Polynomial,
+        scalar::Scalar,
+        trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator, Setup},
+    };
+
+    #[test]
+    fn test_record_and_read_named_commitments() {
+        let dir = std::env::temp_dir().join(format!("kzg-workspace-{}", std::process::id()));
+        let setup = Setup::new(
+            ParallelSetupArtifactsGenerator::new([7; 32]).generate(4),
+            GenerationMode::RandomSecret,
+        )
+        .unwrap();
+
+        let mut workspace = Workspace::open(&dir).unwrap();
+        for (name, coefficients) in [("p", vec![1, 2, 3]), ("q", vec![4, 0, 0, 5])] {
+            let polynomial = Polynomial::try_from(coefficients).unwrap();
+            let commitment = polynomial.commit(&setup.artifacts).unwrap();
+            workspace
+                .record_commitment(
+                    name,
+                    &CommitmentArtifact {
+                        setup_hash: setup.hash().to_owned(),
+                        polynomial,
+                        commitment,
+                        degree_bound: None,
+                    },
+                )
+                .unwrap();
+        }
+
+        let commitment_artifact = workspace.read_commitment("q").unwrap();
+        let evaluation = commitment_artifact
+            .polynomial
+            .evaluate(Scalar::from_i128(2))
+            .unwrap();
+        let proof = evaluation
+            .generate_proof(&commitment_artifact.polynomial, &setup.artifacts)
+            .unwrap();
+        let mut evaluation_artifact = EvaluationArtifact::new(
+            setup.hash().to_owned(),
+            commitment_artifact.commitment.clone(),
+        );
+        evaluation_artifact.add_opening(Opening { evaluation, proof });
+        workspace
+            .record_evaluation("q", &evaluation_artifact)
+            .unwrap();
+
+        let reopened = Workspace::open(&dir).unwrap();
+        let entries: Vec<_> = reopened
+            .entries()
+            .map(|(name, entry)| (name.clone(), entry.degree, entry.evaluation_path.is_some()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![("p".to_owned(), 2, false), ("q".to_owned(), 3, true)]
+        );
+        assert_eq!(
+            reopened.read_evaluation("q").unwrap().openings[0]
+                .evaluation
+                .result,
+            Scalar::from_i128(44)
+        );
+        assert!(reopened.read_evaluation("p").is_err());
+        assert!(reopened.read_commitment("r").is_err());
+
+        let mut reopened = reopened;
+        assert!(
+            reopened
+                .record_commitment("../p", &commitment_artifact)
+                .is_err()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+"""Writes `power_1.ptau`, a BLS12-381 powers of tau file of power 1 in the layout of snarkjs with tau = 2.
+
+The file has the 7 sections written by `snarkjs powersoftau new` and `contribute`: header, tauG1, tauG2,
+alphaTauG1, betaTauG1, betaG2 and the (empty) list of contributions, with alpha = 3 and beta = 5. Points are
+affine, each coordinate in little endian Montgomery form with R = 2^384 as written by ffjavascript.
+The curve arithmetic is implemented here so that the fixture does not depend on the reader under test.
+"""
+import struct
+from pathlib import Path
+
+Q = 0x1A0111EA397FE69A4B1BA7B6434BACD764774B84F38512BF6730D2A0F6B0F6241EABFFFEB153FFFFB9FEFFFFFFFFAAAB
+N8 = 48
+R = 1 << 384
+POWER = 1
+TAU, ALPHA, BETA = 2, 3, 5
+
+G1 = (
+    0x17F1D3A73197D7942695638C4FA9AC0FC3688C4F9774B905A14E3A3F171BAC586C55E83FF97A1AEFFB3AF00ADB22C6BB,
+    0x08B3F481E3AAA0F1A09E30ED741D8AE4FCF5E095D5D00AF600DB18CB2C04B3EDD03CC744A2888AE40CAA232946C5E7E1,
+)
+G2 = (
+    (
+        0x024AA2B2F08F0A91260805272DC51051C6E47AD4FA403B02B4510B647AE3D1770BAC0326A805BBEFD48056C8C121BDB8,
+        0x13E02B6052719F607DACD3A088274F65596BD0D09920B61AB5DA61BBDC7F5049334CF11213945D57E5AC7D055D042B7E,
+    ),
+    (
+        0x0CE5D527727D6E118CC9CDC6DA2E351AADFD9BAA8CBDD3A76D429A695160D12C923AC9CC3BACA289E193548608B82801,
+        0x0606C4A02EA734CC32ACD2B02BC28B99CB3E287E85A763AF267492AB572E99AB3F370D275CEC1DA1AAA9075FF05F79BE,
+    ),
+)
+
+
+class Fp:
+    def __init__(self, v):
+        self.v = v % Q
+
+    def __add__(self, o):
+        return Fp(self.v + o.v)
+
+    def __sub__(self, o):
+        return Fp(self.v - o.v)
+
+    def __mul__(self, o):
+        return Fp(self.v * o.v)
+
+    def inv(self):
+        return Fp(pow(self.v, Q - 2, Q))
+
+    def __eq__(self, o):
+        return self.v == o.v
+
+    def limbs(self):
+        return [self.v]
+
+
+class Fp2:
+    """Elements c0 + c1 i with i^2 = -1"""
+
+    def __init__(self, c0, c1):
+        self.c0, self.c1 = c0 % Q, c1 % Q
+
+    def __add__(self, o):
+        return Fp2(self.c0 + o.c0, self.c1 + o.c1)
+
+    def __sub__(self, o):
+        return Fp2(self.c0 - o.c0, self.c1 - o.c1)
+
+    def __mul__(self, o):
+        return Fp2(self.c0 * o.c0 - self.c1 * o.c1, self.c0 * o.c1 + self.c1 * o.c0)
+
+    def inv(self):
+        norm_inv = pow(self.c0 * self.c0 + self.c1 * self.c1, Q - 2, Q)
+        return Fp2(self.c0 * norm_inv, -self.c1 * norm_inv)
+
+    def __eq__(self, o):
+        return (self.c0, self.c1) == (o.c0, o.c1)
+
+    def limbs(self):
+        return [self.c0, self.c1]
+
+
+def add(p, q):
+    """Affine addition of distinct or equal points, neither being the point at infinity"""
+    (x1, y1), (x2, y2) = p, q
+    if x1 == x2:
+        assert y1 == y2
+        three_x2 = x1 * x1 + x1 * x1 + x1 * x1
+        slope = three_x2 * (y1 + y1).inv()
+    else:
+        slope = (y2 - y1) * (x2 - x1).inv()
+    x3 = slope * slope - x1 - x2
+    return (x3, slope * (x1 - x3) - y1)
+
+
+def mul(p, k):
+    result = None
+    for bit in bin(k)[2:]:
+        result = result if result is None else add(result, result)
+        if bit == "1":
+            result = p if result is None else add(result, p)
+    return result
+
+
+def encode(point):
+    out = b""
+    for coordinate in point:
+        for v in coordinate.limbs():
+            out += (v * R % Q).to_bytes(N8, "little")
+    return out
+
+
+def section(section_type, data):
+    return struct.pack("<IQ", section_type, len(data)) + data
+
+
+g1 = (Fp(G1[0]), Fp(G1[1]))
+g2 = (Fp2(*G2[0]), Fp2(*G2[1]))
+assert g1[1] * g1[1] == g1[0] * g1[0] * g1[0] + Fp(4)
+assert g2[1] * g2[1] == g2[0] * g2[0] * g2[0] + Fp2(4, 4)
+
+tau_g1_count = (1 << (POWER + 1)) - 1
+tau_g2_count = 1 << POWER
+header = struct.pack("<I", N8) + Q.to_bytes(N8, "little") + struct.pack("<II", POWER, POWER)
+tau_g1 = b"".join(encode(mul(g1, TAU**i)) for i in range(tau_g1_count))
+tau_g2 = b"".join(encode(mul(g2, TAU**i)) for i in range(tau_g2_count))
+alpha_tau_g1 = b"".join(encode(mul(g1, ALPHA * TAU**i)) for i in range(tau_g2_count))
+beta_tau_g1 = b"".join(encode(mul(g1, BETA * TAU**i)) for i in range(tau_g2_count))
+beta_g2 = encode(mul(g2, BETA))
+contributions = struct.pack("<I", 0)
+
+content = b"ptau" + struct.pack("<II", 1, 7)
+for i, data in enumerate([header, tau_g1, tau_g2, alpha_tau_g1, beta_tau_g1, beta_g2, contributions]):
+    content += section(i + 1, data)
+Path(__file__).with_name("power_1.ptau").write_bytes(content)
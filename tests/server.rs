@@ -0,0 +1,162 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    thread,
+};
+
+use kzg_poly_commit_exploration::{
+    server::{Server, ServerOptions},
+    trusted_setup::{GenerationMode, ParallelSetupArtifactsGenerator, Setup},
+};
+use serde_json::{Value, json};
+
+/// Starts a service on a free local port and returns its address
+fn start_server(max_body_size: usize) -> SocketAddr {
+    let setup = Setup::new(
+        ParallelSetupArtifactsGenerator::new([7; 32]).generate(5),
+        GenerationMode::RandomSecret,
+    )
+    .unwrap();
+    let server = Server::bind(
+        "127.0.0.1:0",
+        setup,
+        ServerOptions {
+            max_body_size,
+            strict: false,
+        },
+    )
+    .unwrap();
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    address
+}
+
+/// Sends a request and returns the status code along with the JSON body of the response
+fn request(address: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_serve_prove_and_verify() {
+    let address = start_server(4096);
+
+    let (status, health) = request(address, "GET", "/health", "");
+    assert_eq!(status, 200);
+    assert_eq!(health, json!({ "status": "ok" }));
+
+    let (status, info) = request(address, "GET", "/setup/info", "");
+    assert_eq!(status, 200);
+    assert_eq!(info["degree"], 4);
+    assert_eq!(info["insecure"], true);
+
+    let (status, commit) = request(
+        address,
+        "POST",
+        "/commit",
+        r#"{"coefficients": ["1", "2", "3"]}"#,
+    );
+    assert_eq!(status, 200);
+    assert_eq!(commit["polynomial"], "3x^2 + 2x + 1");
+    assert_eq!(commit["degree"], 2);
+    assert_eq!(commit["setup_hash"], info["setup_hash"]);
+
+    let (status, proof) = request(
+        address,
+        "POST",
+        "/prove",
+        r#"{"polynomial": "3x^2 + 2x + 1", "x": "5"}"#,
+    );
+    assert_eq!(status, 200);
+    assert_eq!(proof["commitment"], commit["commitment"]);
+    assert_eq!(proof["y"], "86");
+
+    // The response of `/prove` is accepted as is by `/verify`
+    let (status, verification) = request(address, "POST", "/verify", &proof.to_string());
+    assert_eq!(status, 200);
+    assert_eq!(verification, json!({ "verified": true }));
+
+    let mut wrong = proof.clone();
+    wrong["y"] = json!("87");
+    let batch = json!({ "openings": [proof, wrong] });
+    let (status, verification) = request(address, "POST", "/verify-batch", &batch.to_string());
+    assert_eq!(status, 200);
+    assert_eq!(
+        verification,
+        json!({ "verified": false, "results": [true, false] })
+    );
+}
+
+#[test]
+fn test_serve_errors() {
+    let address = start_server(256);
+
+    let (status, error) = request(address, "GET", "/unknown", "");
+    assert_eq!(status, 404);
+    assert_eq!(error["error"], "not_found");
+
+    let (status, error) = request(address, "GET", "/prove", "");
+    assert_eq!(status, 405);
+    assert_eq!(error["error"], "method_not_allowed");
+
+    let (status, error) = request(address, "POST", "/commit", r#"{"polynomial": "3x^ + 1"}"#);
+    assert_eq!(status, 400);
+    assert_eq!(error["error"], "invalid_input");
+    assert_eq!(
+        error["message"],
+        "Invalid polynomial expression: Expected an exponent at column 5"
+    );
+
+    let (status, error) = request(address, "POST", "/commit", r#"{"polynomial": "x^5"}"#);
+    assert_eq!(status, 400);
+    assert_eq!(error["error"], "invalid_input");
+
+    let (status, error) = request(address, "POST", "/verify", "{}");
+    assert_eq!(status, 400);
+    assert_eq!(error["error"], "invalid_input");
+
+    let large = format!(r#"{{"coefficients": ["{}"]}}"#, "1".repeat(300));
+    let (status, error) = request(address, "POST", "/commit", &large);
+    assert_eq!(status, 413);
+    assert_eq!(error["error"], "payload_too_large");
+}
+
+#[test]
+fn test_serve_rejects_expensive_expressions() {
+    let address = start_server(1 << 20);
+
+    // Deeply nested parentheses are rejected instead of overflowing the stack of the service
+    let nested = format!("{}x{}", "(".repeat(400_000), ")".repeat(400_000));
+    let body = json!({ "polynomial": nested }).to_string();
+    let (status, error) = request(address, "POST", "/commit", &body);
+    assert_eq!(status, 400);
+    assert_eq!(error["error"], "invalid_input");
+
+    // Powers exceeding the degree of the setup are rejected before being expanded
+    let (status, error) = request(
+        address,
+        "POST",
+        "/commit",
+        r#"{"polynomial": "(x + 1)^65536"}"#,
+    );
+    assert_eq!(status, 400);
+    assert_eq!(
+        error["message"],
+        "Invalid polynomial expression: Exponent 65536 exceeds the maximum degree 4 at column 9"
+    );
+
+    let (status, health) = request(address, "GET", "/health", "");
+    assert_eq!(status, 200);
+    assert_eq!(health, json!({ "status": "ok" }));
+}